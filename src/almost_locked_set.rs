@@ -0,0 +1,29 @@
+use std::fmt::Display;
+
+use crate::{candidates::Candidates, unit::Unit, vec2::Vec2};
+
+// n cells inside one unit that hold n + 1 candidates between them
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AlmostLockedSet {
+	pub cells: Vec<Vec2>,
+	pub digits: Candidates,
+	pub unit: Unit,
+}
+
+impl AlmostLockedSet {
+	pub fn overlaps(&self, other: &AlmostLockedSet) -> bool {
+		self.cells.iter().any(|point| other.cells.contains(point))
+	}
+}
+
+impl Display for AlmostLockedSet {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} in {} at", self.digits, self.unit)?;
+
+		for point in self.cells.iter() {
+			write!(f, " {}", point)?;
+		}
+
+		Ok(())
+	}
+}
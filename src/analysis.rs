@@ -1,114 +1,275 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+	cell::OnceCell,
+	collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+};
+
+use image::ImageError;
 
 use crate::{
+	almost_locked_set::AlmostLockedSet,
+	builtin::builtins,
+	candidates::Candidates,
+	chain::{Chain, Link, Node},
+	combinations::combinations,
 	grid::{CellValue, Coord, Grid},
 	grid_image::GridImage,
 	line::LineDirection,
-	square::{Square, SquareIndex},
+	solutions::Solutions,
+	square::Square,
+	step::Step,
+	technique::Technique,
+	unit::Unit,
 	vec2::{Vec2, SUDOKU},
 };
 
+pub(crate) const UNIQUE_RECTANGLES: [&str; 6] = [
+	"unique rectangle type 1",
+	"unique rectangle type 2",
+	"unique rectangle type 3",
+	"unique rectangle type 4",
+	"unique rectangle type 5",
+	"unique rectangle type 6",
+];
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Stepping {
+	#[default]
+	Off,
+	PerRound,
+	PerTechnique,
+	RenderEvery(usize),
+}
+
+impl Stepping {
+	pub fn pauses(&self) -> bool {
+		matches!(self, Stepping::PerRound | Stepping::PerTechnique)
+	}
+}
+
 pub struct Analysis<'a> {
+	// found on first use and dropped whenever the candidates change
+	almost_locked_sets: OnceCell<Vec<AlmostLockedSet>>,
 	cheating: bool,
 	pub grid: &'a mut Grid,
+	pub image_path: String,
+	rounds: usize,
+	pub stepping: Stepping,
+	steps: Vec<Step>,
+	pub techniques: Vec<&'a dyn Technique>,
+	techniques_used: BTreeMap<&'static str, usize>,
+	pub unique: bool,
 }
 
-pub const DEBUG: bool = true;
-
 impl<'a> Analysis<'a> {
 	pub fn new(grid: &'a mut Grid, cheating: bool) -> Self {
-		Analysis { cheating, grid }
+		Analysis {
+			almost_locked_sets: OnceCell::new(),
+			cheating,
+			grid,
+			image_path: String::from("sudoku.png"),
+			rounds: 0,
+			stepping: Stepping::Off,
+			steps: vec![],
+			techniques: builtins(),
+			techniques_used: BTreeMap::new(),
+			unique: false,
+		}
+	}
+
+	pub fn run(&mut self) -> usize {
+		let mut rounds = 0;
+		while self.round() != 0 {
+			rounds += 1;
+		}
+
+		return rounds;
 	}
 
 	pub fn round(&mut self) -> usize {
+		let numbers_inserted = self.techniques();
+		self.rounds += 1;
+
+		match self.stepping {
+			Stepping::PerRound => self.pause(),
+			Stepping::RenderEvery(rounds) if self.rounds.is_multiple_of(rounds) => self.show(&[]),
+			_ => {}
+		}
+
+		return numbers_inserted;
+	}
+
+	fn techniques(&mut self) -> usize {
 		let mut numbers_inserted = 0;
 
-		let answers = self.single_in_squares();
-		for (point, answer) in answers {
-			self.grid.insert_number(&point, answer);
-			numbers_inserted += 1;
+		// the grid is public, so it may have been edited since the last round
+		self.almost_locked_sets.take();
+
+		for technique in self.techniques.clone() {
+			if technique.assumes_unique() && !self.unique {
+				continue;
+			}
+
+			if technique.last_resort() && numbers_inserted != 0 {
+				continue;
+			}
+
+			let steps = technique.find(self);
+			numbers_inserted += self.apply_steps(technique.name(), steps);
 		}
 
-		let answers = self.lonely_cells();
-		for (point, answer) in answers {
-			self.grid.insert_number(&point, answer);
-			numbers_inserted += 1;
+		if self.cheating && numbers_inserted == 0 && !self.grid.is_solved() {
+			numbers_inserted += self.cheat();
 		}
 
-		if self.single_line_in_squares() {
-			numbers_inserted += 1;
+		return numbers_inserted;
+	}
+
+	fn apply_steps(&mut self, technique: &'static str, steps: Vec<Step>) -> usize {
+		let mut changes = 0;
+		let mut applied = vec![];
+
+		for mut step in steps {
+			step.placements.retain(|(point, number)| {
+				if self.grid.has_number(point) || !self.grid.get_candidates(point).contains(*number)
+				{
+					return false;
+				}
+
+				self.grid.insert_number(point, *number);
+				return true;
+			});
+
+			step.eliminations
+				.retain(|(point, candidate)| self.grid.remove_candidate(point, *candidate));
+
+			if step.placements.len() == 0 && step.eliminations.len() == 0 {
+				continue;
+			}
+
+			// steps without a technique of their own are credited to the technique that found them
+			if step.technique.len() == 0 {
+				step.technique = technique;
+			}
+
+			if !applied.contains(&step.technique) {
+				applied.push(step.technique);
+			}
+
+			step.round = self.rounds;
+			self.steps.push(step);
+			changes += 1;
 		}
 
-		if self.square_claim() {
-			numbers_inserted += 1;
+		if changes != 0 {
+			self.almost_locked_sets.take();
 		}
 
-		if self.cheating && numbers_inserted == 0 && self.cheat() {
-			return 0;
+		for technique in applied {
+			self.record_technique(technique);
 		}
 
-		return numbers_inserted;
+		return changes;
 	}
 
-	pub fn verify(&mut self) -> bool {
-		self.grid.verify()
-	}
+	fn record_technique(&mut self, technique: &'static str) {
+		*self.techniques_used.entry(technique).or_default() += 1;
 
-	pub fn calculate_all_candidates(&mut self) {
-		for point in SUDOKU.iter() {
-			if !self.grid.has_number(point) {
-				self.grid.calculate_candidates(point);
+		if self.stepping == Stepping::PerTechnique {
+			let steps = self
+				.steps
+				.iter()
+				.filter(|step| step.round == self.rounds && step.technique == technique)
+				.cloned()
+				.collect::<Vec<_>>();
+
+			for step in steps.iter() {
+				println!("{}", step);
 			}
+
+			self.show(&steps);
+			self.wait();
 		}
 	}
 
-	pub fn single_in_squares(&self) -> Vec<(Vec2, CellValue)> {
-		let mut counts: HashMap<SquareIndex, HashMap<CellValue, Vec<Vec2>>> = HashMap::new();
+	pub fn techniques_used(&self) -> &BTreeMap<&'static str, usize> {
+		&self.techniques_used
+	}
 
-		for point in SUDOKU.iter() {
-			if self.grid.has_number(point) {
-				continue;
-			}
+	pub fn steps(&self) -> &[Step] {
+		&self.steps
+	}
 
-			let candidates = &self.grid.get_candidates(point);
-			let square_index = Square::point_to_index(point);
+	pub fn pause(&self) {
+		self.show(&[]);
+		self.wait();
+	}
 
-			for candidate in candidates.iter() {
-				counts
-					.entry(square_index as SquareIndex)
-					.or_default()
-					.entry(*candidate)
-					.or_default()
-					.push(*point);
-			}
+	// stepping keeps going when the image can't be written, it just says so
+	fn show(&self, steps: &[Step]) {
+		if let Err(error) = self.draw_steps(steps) {
+			eprintln!("{}: could not save image: {}", self.image_path, error);
 		}
+	}
+
+	fn wait(&self) {
+		let mut string = String::new();
+		std::io::stdin().read_line(&mut string).unwrap();
+	}
+
+	pub fn verify(&mut self) -> bool {
+		self.grid.verify()
+	}
+
+	pub fn hidden_singles(&self, units: impl Iterator<Item = Unit>) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for unit in units {
+			let mut positions: HashMap<CellValue, Vec<Vec2>> = HashMap::new();
+			for point in unit.coords().iter() {
+				for candidate in self.grid.get_candidates(point).iter() {
+					positions.entry(candidate).or_default().push(*point);
+				}
+			}
 
-		let mut results = vec![];
-		for maps in counts.values() {
-			for (candidate, coords) in maps.iter() {
-				if coords.len() == 1 {
-					results.push((coords[0], *candidate));
+			for (candidate, coords) in positions {
+				if coords.len() != 1 {
+					continue;
 				}
+
+				steps.push(Step {
+					cells: coords.clone(),
+					digits: [candidate].into_iter().collect(),
+					placements: vec![(coords[0], candidate)],
+					units: vec![unit],
+					..Default::default()
+				});
 			}
 		}
 
-		return results;
+		return steps;
 	}
 
-	pub fn lonely_cells(&self) -> Vec<(Vec2, CellValue)> {
-		let mut results = vec![];
+	pub fn lonely_cells(&self) -> Vec<Step> {
+		let mut steps = vec![];
 
 		for point in SUDOKU.iter() {
 			let candidates = self.grid.get_candidates(point);
-			if candidates.len() == 1 {
-				results.push((*point, candidates[0]));
+			if let Some(candidate) = candidates.first()
+				&& candidates.len() == 1
+			{
+				steps.push(Step {
+					cells: vec![*point],
+					digits: candidates,
+					placements: vec![(*point, candidate)],
+					..Default::default()
+				});
 			}
 		}
 
-		return results;
+		return steps;
 	}
 
-	pub fn single_line_in_squares(&mut self) -> bool {
+	pub fn single_line_in_squares(&self) -> Vec<Step> {
 		let mut single_line_candidates: HashMap<
 			usize,
 			HashMap<(LineDirection, CellValue), HashSet<Coord>>,
@@ -120,7 +281,7 @@ impl<'a> Analysis<'a> {
 
 				for y2 in y * 3..y * 3 + 3 {
 					for x2 in x * 3..x * 3 + 3 {
-						for &candidate in self.grid.get_candidates(&Vec2::new(x2, y2)).iter() {
+						for candidate in self.grid.get_candidates(&Vec2::new(x2, y2)).iter() {
 							single_line_candidates
 								.entry(square_index)
 								.or_default()
@@ -140,10 +301,9 @@ impl<'a> Analysis<'a> {
 			}
 		}
 
-		let mut changes_made = false;
-		let mut candidates_to_remove = vec![];
+		let mut steps = vec![];
 
-		for square in self.grid.squares.iter() {
+		for square in self.grid.squares() {
 			let square_index = Square::square_coord_to_index(square.x, square.y);
 			let Some(single_line_candidates_for_square) = single_line_candidates.get(&square_index)
 			else {
@@ -157,10 +317,12 @@ impl<'a> Analysis<'a> {
 
 				let rank = *set.iter().nth(0).unwrap() as usize;
 				let line = match direction {
-					LineDirection::Row => &self.grid.rows[rank],
-					LineDirection::Column => &self.grid.columns[rank],
+					LineDirection::Row => self.grid.row(rank as Coord),
+					LineDirection::Column => self.grid.column(rank as Coord),
 				};
 
+				let mut eliminations = vec![];
+
 				for point in line.coords() {
 					match direction {
 						LineDirection::Row => {
@@ -175,7 +337,7 @@ impl<'a> Analysis<'a> {
 						}
 					}
 
-					let adjacent_square_index = Square::point_to_index(point);
+					let adjacent_square_index = Square::point_to_index(&point);
 
 					let Some(candidate_map) = single_line_candidates.get(&adjacent_square_index)
 					else {
@@ -186,31 +348,30 @@ impl<'a> Analysis<'a> {
 						continue;
 					};
 
-					if row_set.len() <= 1 {
+					if row_set.len() <= 1 || !self.grid.get_candidates(&point).contains(candidate) {
 						continue;
 					}
 
-					candidates_to_remove.push((*point, candidate));
+					eliminations.push((point, candidate));
 				}
-			}
-		}
 
-		for &(point, candidate) in candidates_to_remove.iter() {
-			let candidates = self.grid.get_candidates(&point);
-			let start = candidates.len();
-
-			self.grid.remove_candidate(&point, candidate);
+				if eliminations.len() == 0 {
+					continue;
+				}
 
-			let candidates = self.grid.get_candidates(&point);
-			if start != candidates.len() {
-				changes_made = true;
+				steps.push(Step {
+					digits: [candidate].into_iter().collect(),
+					eliminations,
+					units: vec![square.unit(), line.unit()],
+					..Default::default()
+				});
 			}
 		}
 
-		return changes_made;
+		return steps;
 	}
 
-	pub fn square_claim(&mut self) -> bool {
+	pub fn square_claim(&self) -> Vec<Step> {
 		let mut exclusives = HashMap::new();
 
 		for line in self.grid.lines() {
@@ -238,13 +399,15 @@ impl<'a> Analysis<'a> {
 			}
 		}
 
-		let mut changes_made = false;
+		let mut steps = vec![];
 
 		for ((rank, direction, number), square_rank) in exclusives {
 			let Some(square_rank) = square_rank else {
 				continue;
 			};
 
+			let mut eliminations = vec![];
+
 			let start_rank = (rank / 3) * 3;
 			let end_rank = start_rank + 3;
 
@@ -259,73 +422,1987 @@ impl<'a> Analysis<'a> {
 						LineDirection::Column => Vec2::new(i, square_rank * 3 + candidate_index),
 					};
 
-					if self.grid.remove_candidate(&point, number) {
-						changes_made = true;
+					if self.grid.get_candidates(&point).contains(number) {
+						eliminations.push((point, number));
 					}
 				}
 			}
+
+			if eliminations.len() == 0 {
+				continue;
+			}
+
+			let (line, square) = match direction {
+				LineDirection::Row => (
+					self.grid.row(rank),
+					Square::new(self.grid, square_rank, rank / 3),
+				),
+				LineDirection::Column => (
+					self.grid.column(rank),
+					Square::new(self.grid, rank / 3, square_rank),
+				),
+			};
+
+			steps.push(Step {
+				digits: [number].into_iter().collect(),
+				eliminations,
+				units: vec![line.unit(), square.unit()],
+				..Default::default()
+			});
 		}
 
-		return changes_made;
+		return steps;
 	}
 
-	pub fn cheat(&mut self) -> bool {
-		let mut best_square = &self.grid.squares[0];
-		for square in self.grid.squares.iter() {
-			if (square.cells.len() > best_square.cells.len() && square.cells.len() != 9)
-				|| best_square.cells.len() == 0
-			{
-				best_square = square;
+	pub fn naked_subsets(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for unit in Unit::all() {
+			let empty = unit
+				.coords()
+				.into_iter()
+				.filter(|point| !self.grid.has_number(point))
+				.collect::<Vec<_>>();
+
+			for size in 2..=4 {
+				if empty.len() <= size {
+					break;
+				}
+
+				let cells = empty
+					.iter()
+					.filter(|point| self.grid.get_candidates(point).len() <= size)
+					.copied()
+					.collect::<Vec<_>>();
+
+				for subset in combinations(&cells, size) {
+					let digits = subset.iter().fold(Candidates::new(), |digits, point| {
+						digits | self.grid.get_candidates(point)
+					});
+
+					if digits.len() != size {
+						continue;
+					}
+
+					let mut eliminations = vec![];
+					for point in empty.iter().filter(|point| !subset.contains(point)) {
+						for digit in (self.grid.get_candidates(point) & digits).iter() {
+							eliminations.push((*point, digit));
+						}
+					}
+
+					steps.push(Step {
+						cells: subset,
+						digits,
+						eliminations,
+						units: vec![unit],
+						..Default::default()
+					});
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn hidden_subsets(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for unit in Unit::all() {
+			let coords = unit.coords();
+
+			let mut positions: HashMap<CellValue, Vec<Vec2>> = HashMap::new();
+			for point in coords.iter() {
+				for candidate in self.grid.get_candidates(point).iter() {
+					positions.entry(candidate).or_default().push(*point);
+				}
+			}
+
+			let empty = coords
+				.iter()
+				.filter(|point| !self.grid.has_number(point))
+				.count();
+
+			for size in 2..=4 {
+				if empty <= size {
+					break;
+				}
+
+				let mut digits = positions
+					.iter()
+					.filter(|(_, points)| points.len() <= size)
+					.map(|(&digit, _)| digit)
+					.collect::<Vec<_>>();
+				digits.sort();
+
+				for subset in combinations(&digits, size) {
+					let mut cells = subset
+						.iter()
+						.flat_map(|digit| positions[digit].iter().copied())
+						.collect::<Vec<_>>();
+					cells.sort_by_key(|point| (point.y, point.x));
+					cells.dedup();
+
+					if cells.len() != size {
+						continue;
+					}
+
+					let digits = subset.iter().copied().collect::<Candidates>();
+
+					let mut eliminations = vec![];
+					for point in cells.iter() {
+						for candidate in (self.grid.get_candidates(point) - digits).iter() {
+							eliminations.push((*point, candidate));
+						}
+					}
+
+					steps.push(Step {
+						cells,
+						digits,
+						eliminations,
+						units: vec![unit],
+						..Default::default()
+					});
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn fish(&self, size: usize) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for direction in [LineDirection::Row, LineDirection::Column] {
+			let cover_direction = direction.perpendicular();
+
+			for number in 1..=9 {
+				let lines = self
+					.grid
+					.lines()
+					.into_iter()
+					.filter(|line| line.direction == direction)
+					.map(|line| (line, line.candidate_coords(number)))
+					.filter(|(_, coords)| coords.len() >= 2 && coords.len() <= size)
+					.collect::<Vec<_>>();
+
+				let indices = (0..lines.len()).collect::<Vec<_>>();
+				for subset in combinations(&indices, size) {
+					let base = subset
+						.iter()
+						.map(|&index| &lines[index])
+						.collect::<Vec<_>>();
+
+					let cover_ranks = base
+						.iter()
+						.flat_map(|(_, coords)| {
+							coords.iter().map(|point| cover_direction.rank(point))
+						})
+						.collect::<BTreeSet<Coord>>();
+
+					if cover_ranks.len() != size {
+						continue;
+					}
+
+					let covers = cover_ranks
+						.iter()
+						.map(|&rank| match cover_direction {
+							LineDirection::Row => self.grid.row(rank),
+							LineDirection::Column => self.grid.column(rank),
+						})
+						.collect::<Vec<_>>();
+
+					let cells = base
+						.iter()
+						.flat_map(|(_, coords)| coords.iter().copied())
+						.collect::<Vec<_>>();
+
+					let mut eliminations = vec![];
+					for cover in covers.iter() {
+						for point in cover.candidate_coords(number) {
+							if !cells.contains(&point) {
+								eliminations.push((point, number));
+							}
+						}
+					}
+
+					steps.push(Step {
+						cells,
+						digits: [number].into_iter().collect(),
+						eliminations,
+						units: base
+							.iter()
+							.map(|(line, _)| line.unit())
+							.chain(covers.iter().map(|line| line.unit()))
+							.collect(),
+						..Default::default()
+					});
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn finned_fish(&self, size: usize, sashimi: bool) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for direction in [LineDirection::Row, LineDirection::Column] {
+			let cover_direction = direction.perpendicular();
+
+			for number in 1..=9 {
+				let lines = self
+					.grid
+					.lines()
+					.into_iter()
+					.filter(|line| line.direction == direction)
+					.map(|line| (line, line.candidate_coords(number)))
+					.filter(|(_, coords)| coords.len() >= 1 && coords.len() <= size + 2)
+					.collect::<Vec<_>>();
+
+				let indices = (0..lines.len()).collect::<Vec<_>>();
+				for subset in combinations(&indices, size) {
+					let base = subset
+						.iter()
+						.map(|&index| &lines[index])
+						.collect::<Vec<_>>();
+
+					let ranks = base
+						.iter()
+						.flat_map(|(_, coords)| {
+							coords.iter().map(|point| cover_direction.rank(point))
+						})
+						.collect::<BTreeSet<Coord>>()
+						.into_iter()
+						.collect::<Vec<_>>();
+
+					if ranks.len() <= size {
+						continue;
+					}
+
+					for cover_ranks in combinations(&ranks, size) {
+						let in_cover =
+							|point: &Vec2| cover_ranks.contains(&cover_direction.rank(point));
+
+						let fins = base
+							.iter()
+							.flat_map(|(_, coords)| coords.iter().copied())
+							.filter(|point| !in_cover(point))
+							.collect::<Vec<_>>();
+
+						let fin_square = Square::point_to_index(&fins[0]);
+						if fins
+							.iter()
+							.any(|point| Square::point_to_index(point) != fin_square)
+						{
+							continue;
+						}
+
+						let body = base
+							.iter()
+							.map(|(_, coords)| {
+								coords.iter().copied().filter(in_cover).collect::<Vec<_>>()
+							})
+							.collect::<Vec<_>>();
+
+						if body.iter().any(|coords| coords.len() == 0) {
+							continue;
+						}
+
+						if body.iter().any(|coords| coords.len() == 1) != sashimi {
+							continue;
+						}
+
+						let covers = cover_ranks
+							.iter()
+							.map(|&rank| match cover_direction {
+								LineDirection::Row => self.grid.row(rank),
+								LineDirection::Column => self.grid.column(rank),
+							})
+							.collect::<Vec<_>>();
+
+						let mut eliminations = vec![];
+						for cover in covers.iter() {
+							for point in cover.candidate_coords(number) {
+								let in_base = base
+									.iter()
+									.any(|(line, _)| line.rank() == direction.rank(&point));
+								if !in_base && Square::point_to_index(&point) == fin_square {
+									eliminations.push((point, number));
+								}
+							}
+						}
+
+						if eliminations.len() == 0 {
+							continue;
+						}
+
+						steps.push(Step {
+							cells: body.into_iter().flatten().collect(),
+							digits: [number].into_iter().collect(),
+							eliminations,
+							fins,
+							units: base
+								.iter()
+								.map(|(line, _)| line.unit())
+								.chain(covers.iter().map(|line| line.unit()))
+								.collect(),
+							..Default::default()
+						});
+					}
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	fn cells_with_candidates(&self, count: usize) -> Vec<Vec2> {
+		SUDOKU
+			.iter()
+			.filter(|point| self.grid.get_candidates(point).len() == count)
+			.copied()
+			.collect()
+	}
+
+	fn eliminations_seen_by(&self, cells: &[Vec2], number: CellValue) -> Vec<(Vec2, CellValue)> {
+		SUDOKU
+			.iter()
+			.filter(|point| !cells.contains(point))
+			.filter(|point| self.grid.get_candidates(point).contains(number))
+			.filter(|point| cells.iter().all(|cell| Grid::sees(point, cell)))
+			.map(|point| (*point, number))
+			.collect()
+	}
+
+	pub fn xy_wings(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		let bivalues = self.cells_with_candidates(2);
+		for pivot in bivalues.iter() {
+			let pivot_candidates = self.grid.get_candidates(pivot);
+
+			let pincers = bivalues
+				.iter()
+				.filter(|point| Grid::sees(pivot, point))
+				.filter(|point| (self.grid.get_candidates(point) & pivot_candidates).len() == 1)
+				.copied()
+				.collect::<Vec<_>>();
+
+			for pair in combinations(&pincers, 2) {
+				let (first, second) = (
+					self.grid.get_candidates(&pair[0]),
+					self.grid.get_candidates(&pair[1]),
+				);
+				let shared = first & second;
+				if shared.len() != 1 || (first | second | pivot_candidates).len() != 3 {
+					continue;
+				}
+
+				let Some(number) = (shared - pivot_candidates).first() else {
+					continue;
+				};
+
+				steps.push(Step {
+					cells: vec![*pivot, pair[0], pair[1]],
+					digits: shared,
+					eliminations: self.eliminations_seen_by(&pair, number),
+					..Default::default()
+				});
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn xyz_wings(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		let bivalues = self.cells_with_candidates(2);
+		for pivot in self.cells_with_candidates(3).iter() {
+			let pivot_candidates = self.grid.get_candidates(pivot);
+
+			let pincers = bivalues
+				.iter()
+				.filter(|point| Grid::sees(pivot, point))
+				.filter(|point| (self.grid.get_candidates(point) - pivot_candidates).is_empty())
+				.copied()
+				.collect::<Vec<_>>();
+
+			for pair in combinations(&pincers, 2) {
+				let shared =
+					self.grid.get_candidates(&pair[0]) & self.grid.get_candidates(&pair[1]);
+				let Some(number) = shared.first() else {
+					continue;
+				};
+
+				if shared.len() != 1 {
+					continue;
+				}
+
+				steps.push(Step {
+					cells: vec![*pivot, pair[0], pair[1]],
+					digits: shared,
+					eliminations: self.eliminations_seen_by(&[*pivot, pair[0], pair[1]], number),
+					..Default::default()
+				});
 			}
 		}
 
-		let mut candidate_coords = Vec2::new(0, 0);
-		let mut candidates = vec![];
+		return steps;
+	}
+
+	pub fn w_wings(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		let bivalues = self.cells_with_candidates(2);
+		for pair in combinations(&bivalues, 2) {
+			let candidates = self.grid.get_candidates(&pair[0]);
+			if candidates != self.grid.get_candidates(&pair[1]) || Grid::sees(&pair[0], &pair[1]) {
+				continue;
+			}
+
+			for link_number in candidates.iter() {
+				let Some(number) = (candidates - [link_number].into_iter().collect()).first()
+				else {
+					continue;
+				};
 
-		for point in best_square.coords() {
-			let candidates2 = self.grid.get_candidates(point);
-			if candidates2.len() != 0 {
-				candidate_coords = *point;
-				candidates = candidates2.clone();
-				break;
+				for unit in Unit::all() {
+					let ends = unit
+						.coords()
+						.into_iter()
+						.filter(|point| self.grid.get_candidates(point).contains(link_number))
+						.collect::<Vec<_>>();
+
+					if ends.len() != 2 || ends.iter().any(|point| pair.contains(point)) {
+						continue;
+					}
+
+					let linked = (Grid::sees(&ends[0], &pair[0]) && Grid::sees(&ends[1], &pair[1]))
+						|| (Grid::sees(&ends[0], &pair[1]) && Grid::sees(&ends[1], &pair[0]));
+					if !linked {
+						continue;
+					}
+
+					steps.push(Step {
+						cells: vec![pair[0], pair[1], ends[0], ends[1]],
+						digits: candidates,
+						eliminations: self.eliminations_seen_by(&pair, number),
+						units: vec![unit],
+						..Default::default()
+					});
+				}
 			}
 		}
 
-		let mut grids = vec![];
+		return steps;
+	}
+
+	pub fn conjugate_pairs(&self, number: CellValue) -> Vec<(Unit, Vec2, Vec2)> {
+		let mut pairs = vec![];
 
-		for &candidate in candidates.iter() {
-			let mut new_grid = self.grid.clone();
-			new_grid.insert_number(&candidate_coords, candidate);
+		for line in self.grid.lines() {
+			let coords = line.candidate_coords(number);
+			if coords.len() == 2 {
+				pairs.push((line.unit(), coords[0], coords[1]));
+			}
+		}
 
-			grids.push(new_grid);
+		for square in self.grid.squares() {
+			let coords = square.candidate_coords(number);
+			if coords.len() == 2 {
+				pairs.push((square.unit(), coords[0], coords[1]));
+			}
 		}
 
-		for mut grid in grids.iter_mut() {
-			let mut analysis = Analysis::new(&mut grid, self.cheating);
-			while analysis.round() != 0 && analysis.verify() {
-				if DEBUG {
-					analysis.draw();
+		return pairs;
+	}
+
+	pub fn skyscrapers(&self) -> Vec<Step> {
+		let mut steps = vec![];
 
-					let mut string = String::new();
-					std::io::stdin().read_line(&mut string).unwrap();
+		for number in 1..=9 {
+			for direction in [LineDirection::Row, LineDirection::Column] {
+				let cross = direction.perpendicular();
+
+				let pairs = self
+					.conjugate_pairs(number)
+					.into_iter()
+					.filter(|(unit, _, _)| match direction {
+						LineDirection::Row => matches!(unit, Unit::Row(_)),
+						LineDirection::Column => matches!(unit, Unit::Column(_)),
+					})
+					.collect::<Vec<_>>();
+
+				for (index, first) in pairs.iter().enumerate() {
+					for second in pairs.iter().skip(index + 1) {
+						for (base, roof) in [(first.1, first.2), (first.2, first.1)] {
+							for (other_base, other_roof) in
+								[(second.1, second.2), (second.2, second.1)]
+							{
+								if cross.rank(&base) != cross.rank(&other_base)
+									|| cross.rank(&roof) == cross.rank(&other_roof)
+								{
+									continue;
+								}
+
+								steps.push(Step {
+									cells: vec![base, roof, other_base, other_roof],
+									digits: [number].into_iter().collect(),
+									eliminations: self
+										.eliminations_seen_by(&[roof, other_roof], number),
+									units: vec![first.0, second.0],
+									..Default::default()
+								});
+							}
+						}
+					}
 				}
 			}
+		}
+
+		return steps;
+	}
+
+	pub fn two_string_kites(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for number in 1..=9 {
+			let pairs = self.conjugate_pairs(number);
+			let rows = pairs
+				.iter()
+				.filter(|(unit, _, _)| matches!(unit, Unit::Row(_)));
+
+			for row in rows {
+				let columns = pairs
+					.iter()
+					.filter(|(unit, _, _)| matches!(unit, Unit::Column(_)));
+				for column in columns {
+					for (row_end, row_other) in [(row.1, row.2), (row.2, row.1)] {
+						for (column_end, column_other) in
+							[(column.1, column.2), (column.2, column.1)]
+						{
+							let distinct = [row_end, row_other, column_end, column_other]
+								.iter()
+								.collect::<HashSet<_>>()
+								.len() == 4;
 
-			if DEBUG {
-				analysis.draw();
+							if !distinct
+								|| Square::point_to_index(&row_end)
+									!= Square::point_to_index(&column_end)
+							{
+								continue;
+							}
 
-				let mut string = String::new();
-				std::io::stdin().read_line(&mut string).unwrap();
+							steps.push(Step {
+								cells: vec![row_other, row_end, column_end, column_other],
+								digits: [number].into_iter().collect(),
+								eliminations: self
+									.eliminations_seen_by(&[row_other, column_other], number),
+								units: vec![row.0, column.0],
+								..Default::default()
+							});
+						}
+					}
+				}
 			}
 		}
 
-		return true;
+		return steps;
 	}
 
-	pub fn draw(&self) {
-		let mut grid_image = GridImage::new(&self.grid);
-		grid_image.create_image();
-		grid_image.draw_candidates();
-		grid_image.save();
+	pub fn empty_rectangles(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for number in 1..=9 {
+			let pairs = self.conjugate_pairs(number);
+
+			for square in self.grid.squares() {
+				let coords = square.candidate_coords(number);
+				if coords.len() < 2 {
+					continue;
+				}
+
+				for row in square.y * 3..square.y * 3 + 3 {
+					for column in square.x * 3..square.x * 3 + 3 {
+						if coords
+							.iter()
+							.any(|point| point.x != column && point.y != row)
+						{
+							continue;
+						}
+
+						for (unit, first, second) in pairs.iter() {
+							for (end, other) in [(*first, *second), (*second, *first)] {
+								let target = match unit {
+									Unit::Column(_) if end.y == row => Vec2::new(column, other.y),
+									Unit::Row(_) if end.x == column => Vec2::new(other.x, row),
+									_ => continue,
+								};
+
+								let square_unit = square.unit();
+								if square_unit.contains(&end)
+									|| square_unit.contains(&other)
+									|| square_unit.contains(&target)
+									|| !self.grid.get_candidates(&target).contains(number)
+								{
+									continue;
+								}
+
+								let mut cells = coords.clone();
+								cells.push(end);
+								cells.push(other);
+
+								steps.push(Step {
+									cells,
+									digits: [number].into_iter().collect(),
+									eliminations: vec![(target, number)],
+									units: vec![square_unit, *unit],
+									..Default::default()
+								});
+							}
+						}
+					}
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn color_clusters(&self, number: CellValue) -> Vec<[Vec<Vec2>; 2]> {
+		let pairs = self.conjugate_pairs(number);
+
+		let mut links: HashMap<Vec2, Vec<Vec2>> = HashMap::new();
+		for (_, first, second) in pairs.iter() {
+			links.entry(*first).or_default().push(*second);
+			links.entry(*second).or_default().push(*first);
+		}
+
+		let mut clusters = vec![];
+		let mut colored: HashSet<Vec2> = HashSet::new();
+		for (_, start, _) in pairs.iter() {
+			if colored.contains(start) {
+				continue;
+			}
+
+			let mut cluster: [Vec<Vec2>; 2] = [vec![], vec![]];
+			let mut queue = VecDeque::from([(*start, 0)]);
+			colored.insert(*start);
+
+			while let Some((point, color)) = queue.pop_front() {
+				cluster[color].push(point);
+
+				for other in links[&point].iter() {
+					if colored.insert(*other) {
+						queue.push_back((*other, 1 - color));
+					}
+				}
+			}
+
+			clusters.push(cluster);
+		}
+
+		return clusters;
+	}
+
+	pub fn color_wraps(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for number in 1..=9 {
+			for cluster in self.color_clusters(number) {
+				for color in 0..2 {
+					let wrapped = combinations(&cluster[color], 2)
+						.iter()
+						.any(|pair| Grid::sees(&pair[0], &pair[1]));
+
+					if !wrapped {
+						continue;
+					}
+
+					steps.push(Step {
+						colors: cluster.to_vec(),
+						digits: [number].into_iter().collect(),
+						eliminations: cluster[color]
+							.iter()
+							.map(|point| (*point, number))
+							.collect(),
+						..Default::default()
+					});
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn color_traps(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for number in 1..=9 {
+			for cluster in self.color_clusters(number) {
+				let eliminations = SUDOKU
+					.iter()
+					.filter(|point| self.grid.get_candidates(point).contains(number))
+					.filter(|point| !cluster[0].contains(point) && !cluster[1].contains(point))
+					.filter(|point| {
+						cluster
+							.iter()
+							.all(|color| color.iter().any(|cell| Grid::sees(point, cell)))
+					})
+					.map(|point| (*point, number))
+					.collect::<Vec<_>>();
+
+				if eliminations.len() == 0 {
+					continue;
+				}
+
+				steps.push(Step {
+					colors: cluster.to_vec(),
+					digits: [number].into_iter().collect(),
+					eliminations,
+					..Default::default()
+				});
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn multi_coloring(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for number in 1..=9 {
+			let clusters = self.color_clusters(number);
+
+			for (index, first) in clusters.iter().enumerate() {
+				for second in clusters.iter().skip(index + 1) {
+					for (color, other_color) in [(0, 0), (0, 1), (1, 0), (1, 1)] {
+						let sees = |cells: &[Vec2], others: &[Vec2]| {
+							cells
+								.iter()
+								.any(|cell| others.iter().any(|other| Grid::sees(cell, other)))
+						};
+
+						if !sees(&first[color], &second[other_color]) {
+							continue;
+						}
+
+						// both colors cannot be true, so one of their opposites must be
+						let (opposite, other_opposite) =
+							(&first[1 - color], &second[1 - other_color]);
+
+						let mut eliminations = SUDOKU
+							.iter()
+							.filter(|point| self.grid.get_candidates(point).contains(number))
+							.filter(|point| {
+								!first
+									.iter()
+									.chain(second.iter())
+									.any(|color| color.contains(point))
+							})
+							.filter(|point| {
+								opposite.iter().any(|cell| Grid::sees(point, cell))
+									&& other_opposite.iter().any(|cell| Grid::sees(point, cell))
+							})
+							.map(|point| (*point, number))
+							.collect::<Vec<_>>();
+
+						// a color that sees both colors of another cluster is false
+						if sees(&first[color], &second[1 - other_color]) {
+							eliminations.extend(first[color].iter().map(|point| (*point, number)));
+						}
+
+						if eliminations.len() == 0 {
+							continue;
+						}
+
+						steps.push(Step {
+							colors: vec![
+								first[0].clone(),
+								first[1].clone(),
+								second[0].clone(),
+								second[1].clone(),
+							],
+							digits: [number].into_iter().collect(),
+							eliminations,
+							..Default::default()
+						});
+					}
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn sue_de_coq(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for line in self.grid.lines() {
+			for mini_line in line.mini_lines.iter() {
+				let square = mini_line.square();
+				let empty = |coords: Vec<Vec2>| {
+					coords
+						.into_iter()
+						.filter(|point| !self.grid.has_number(point))
+						.collect::<Vec<_>>()
+				};
+
+				let intersection = empty(mini_line.coords().collect());
+				let outside = |point: &Vec2| !mini_line.coords().any(|other| other == *point);
+				let line_rest = empty(line.coords().filter(outside).collect());
+				let square_rest = empty(square.coords().filter(outside).collect());
+
+				let union = |cells: &[Vec2]| {
+					cells.iter().fold(Candidates::new(), |digits, point| {
+						digits | self.grid.get_candidates(point)
+					})
+				};
+
+				for size in 2..=intersection.len() {
+					for cells in combinations(&intersection, size) {
+						let digits = union(&cells);
+						if digits.len() < size + 2 {
+							continue;
+						}
+
+						for line_size in 1..line_rest.len() {
+							for line_cells in combinations(&line_rest, line_size) {
+								let line_digits = union(&line_cells);
+
+								for square_size in 1..square_rest.len() {
+									for square_cells in combinations(&square_rest, square_size) {
+										let square_digits = union(&square_cells);
+
+										// every digit fits at most once into the cells, so with as many
+										// digits as cells each of them is placed exactly once
+										let all = digits | line_digits | square_digits;
+										if !(line_digits & square_digits).is_empty()
+											|| all.len() != size + line_size + square_size
+										{
+											continue;
+										}
+
+										let line_locked = all - square_digits;
+										let square_locked = all - line_digits;
+
+										let mut eliminations = vec![];
+										for point in line_rest
+											.iter()
+											.filter(|point| !line_cells.contains(point))
+										{
+											for number in (self.grid.get_candidates(point)
+												& line_locked)
+												.iter()
+											{
+												eliminations.push((*point, number));
+											}
+										}
+
+										for point in square_rest
+											.iter()
+											.filter(|point| !square_cells.contains(point))
+										{
+											for number in (self.grid.get_candidates(point)
+												& square_locked)
+												.iter()
+											{
+												eliminations.push((*point, number));
+											}
+										}
+
+										for point in intersection
+											.iter()
+											.filter(|point| !cells.contains(point))
+										{
+											for number in
+												(self.grid.get_candidates(point) & all).iter()
+											{
+												eliminations.push((*point, number));
+											}
+										}
+
+										if eliminations.len() == 0 {
+											continue;
+										}
+
+										steps.push(Step {
+											cells: cells
+												.iter()
+												.chain(line_cells.iter())
+												.chain(square_cells.iter())
+												.copied()
+												.collect(),
+											digits,
+											eliminations,
+											units: vec![line.unit(), square.unit()],
+											..Default::default()
+										});
+									}
+								}
+							}
+						}
+					}
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn nishio(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for number in 1..=9 {
+			for point in SUDOKU.iter() {
+				if !self.grid.get_candidates(point).contains(number) {
+					continue;
+				}
+
+				if self.digit_holds(number, *point) {
+					continue;
+				}
+
+				steps.push(Step {
+					cells: vec![*point],
+					digits: [number].into_iter().collect(),
+					eliminations: vec![(*point, number)],
+					..Default::default()
+				});
+			}
+		}
+
+		return steps;
+	}
+
+	// places `number` at `point` and keeps placing it wherever a unit has one spot left, false
+	// once some unit has nowhere left to put it
+	fn digit_holds(&self, number: CellValue, point: Vec2) -> bool {
+		let mut placed = SUDOKU
+			.iter()
+			.filter(|other| self.grid.get_number(other) == number)
+			.copied()
+			.collect::<HashSet<_>>();
+		let mut positions = SUDOKU
+			.iter()
+			.filter(|other| self.grid.get_candidates(other).contains(number))
+			.copied()
+			.collect::<HashSet<_>>();
+
+		let mut queue = vec![point];
+		while let Some(point) = queue.pop() {
+			if !positions.contains(&point) {
+				continue;
+			}
+
+			placed.insert(point);
+			positions.retain(|other| *other != point && !Grid::sees(&point, other));
+
+			for unit in Unit::all() {
+				if unit.coords().iter().any(|other| placed.contains(other)) {
+					continue;
+				}
+
+				let spots = unit
+					.coords()
+					.into_iter()
+					.filter(|other| positions.contains(other))
+					.collect::<Vec<_>>();
+
+				match spots.len() {
+					0 => return false,
+					1 => queue.push(spots[0]),
+					_ => {}
+				}
+			}
+		}
+
+		return true;
+	}
+
+	pub fn templates(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for number in 1..=9 {
+			// cells used by any template and cells used by all of them
+			let mut union = 0u128;
+			let mut intersection = u128::MAX;
+			self.template(number, &mut vec![], &mut union, &mut intersection);
+
+			if union == 0 {
+				continue;
+			}
+
+			let mut eliminations = vec![];
+			for point in SUDOKU.iter() {
+				let candidates = self.grid.get_candidates(point);
+				if !candidates.contains(number) {
+					continue;
+				}
+
+				let bit = Analysis::cell_bit(point);
+				if union & bit == 0 {
+					eliminations.push((*point, number));
+				} else if intersection & bit != 0 {
+					for other in (candidates - [number].into_iter().collect()).iter() {
+						eliminations.push((*point, other));
+					}
+				}
+			}
+
+			if eliminations.len() == 0 {
+				continue;
+			}
+
+			steps.push(Step {
+				digits: [number].into_iter().collect(),
+				eliminations,
+				..Default::default()
+			});
+		}
+
+		return steps;
+	}
+
+	// every way of placing `number` once per row, column and square, filled in a row at a time
+	fn template(
+		&self,
+		number: CellValue,
+		current: &mut Vec<Vec2>,
+		union: &mut u128,
+		intersection: &mut u128,
+	) {
+		let y = current.len() as Coord;
+		if y == 9 {
+			let template = current
+				.iter()
+				.fold(0u128, |mask, point| mask | Analysis::cell_bit(point));
+			*union |= template;
+			*intersection &= template;
+			return;
+		}
+
+		let row = self.grid.row(y);
+		let given = row
+			.coords()
+			.find(|point| self.grid.get_number(point) == number);
+
+		for point in row.coords() {
+			let possible = match given {
+				Some(given) => point == given,
+				None => self.grid.get_candidates(&point).contains(number),
+			};
+
+			if !possible || current.iter().any(|other| Grid::sees(&point, other)) {
+				continue;
+			}
+
+			current.push(point);
+			self.template(number, current, union, intersection);
+			current.pop();
+		}
+	}
+
+	// one bit per cell, so a set of cells fits in a u128
+	fn cell_bit(point: &Vec2) -> u128 {
+		1 << (point.y as u128 * 9 + point.x as u128)
+	}
+
+	pub fn strong_links(&self) -> HashMap<Node, Vec<Node>> {
+		let mut links: HashMap<Node, Vec<Node>> = HashMap::new();
+
+		for number in 1..=9 {
+			for (_, first, second) in self.conjugate_pairs(number) {
+				links
+					.entry((first, number))
+					.or_default()
+					.push((second, number));
+				links
+					.entry((second, number))
+					.or_default()
+					.push((first, number));
+			}
+		}
+
+		for point in self.cells_with_candidates(2) {
+			let candidates = self.grid.get_candidates(&point).iter().collect::<Vec<_>>();
+			links
+				.entry((point, candidates[0]))
+				.or_default()
+				.push((point, candidates[1]));
+			links
+				.entry((point, candidates[1]))
+				.or_default()
+				.push((point, candidates[0]));
+		}
+
+		for nodes in links.values_mut() {
+			nodes.sort_by_key(|(point, number)| (point.y, point.x, *number));
+			nodes.dedup();
+		}
+
+		return links;
+	}
+
+	pub fn weak_links(&self) -> HashMap<Node, Vec<Node>> {
+		let mut links: HashMap<Node, Vec<Node>> = HashMap::new();
+
+		for point in SUDOKU.iter() {
+			let candidates = self.grid.get_candidates(point);
+			for number in candidates.iter() {
+				let nodes = links.entry((*point, number)).or_default();
+
+				for other in (candidates - [number].into_iter().collect()).iter() {
+					nodes.push((*point, other));
+				}
+
+				for peer in Grid::peers(point) {
+					if self.grid.get_candidates(&peer).contains(number) {
+						nodes.push((peer, number));
+					}
+				}
+			}
+		}
+
+		return links;
+	}
+
+	pub fn alternating_chains(&self) -> Vec<Step> {
+		const MAX_NODES: usize = 16;
+
+		let strong = self.strong_links();
+		let weak = self.weak_links();
+
+		let mut starts = strong.keys().copied().collect::<Vec<_>>();
+		starts.sort_by_key(|(point, number)| (point.y, point.x, *number));
+
+		let mut steps = vec![];
+		let mut eliminated: HashSet<Node> = HashSet::new();
+		for start in starts {
+			// a state is a node and the link that reached it, the next link has to be the opposite
+			let mut parents: HashMap<(Node, Link), (Node, Link)> = HashMap::new();
+			let mut queue = VecDeque::from([((start, Link::Weak), 1)]);
+
+			while let Some(((node, link), length)) = queue.pop_front() {
+				if link == Link::Strong {
+					let eliminations = weak[&node]
+						.iter()
+						.filter(|other| **other != start && weak[&start].contains(other))
+						.copied()
+						.collect::<Vec<_>>();
+
+					if eliminations.iter().any(|other| !eliminated.contains(other))
+						&& let Some(chain) = Self::build_chain(&parents, start, (node, link))
+					{
+						eliminated.extend(eliminations.iter().copied());
+
+						steps.push(Step {
+							eliminations,
+							technique: chain.kind(),
+							chain: Some(chain),
+							..Default::default()
+						});
+					}
+				}
+
+				if length == MAX_NODES || (node == start && length > 1) {
+					continue;
+				}
+
+				let next = link.opposite();
+				let neighbours = match next {
+					Link::Strong => strong.get(&node),
+					Link::Weak => weak.get(&node),
+				};
+
+				for neighbour in neighbours.into_iter().flatten() {
+					let state = (*neighbour, next);
+					if state.0 == start && next == Link::Weak || parents.contains_key(&state) {
+						continue;
+					}
+
+					parents.insert(state, (node, link));
+					queue.push_back((state, length + 1));
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	fn build_chain(
+		parents: &HashMap<(Node, Link), (Node, Link)>,
+		start: Node,
+		end: (Node, Link),
+	) -> Option<Chain> {
+		let mut chain = Chain::default();
+
+		let mut state = end;
+		while let Some(parent) = parents.get(&state) {
+			chain.nodes.push(state.0);
+			chain.links.push(state.1);
+			state = *parent;
+		}
+		chain.nodes.push(start);
+
+		chain.nodes.reverse();
+		chain.links.reverse();
+
+		// a node may only repeat when the chain loops back to where it started
+		let inner = &chain.nodes[..chain.nodes.len() - if end.0 == start { 1 } else { 0 }];
+		if inner.iter().collect::<HashSet<_>>().len() != inner.len() {
+			return None;
+		}
+
+		return Some(chain);
+	}
+
+	// only sound when the puzzle has a single solution, so these are gated behind `unique`
+	pub fn unique_rectangles(&self) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for rows in combinations(&(0..9).collect::<Vec<Coord>>(), 2) {
+			for columns in combinations(&(0..9).collect::<Vec<Coord>>(), 2) {
+				if (rows[0] / 3 == rows[1] / 3) == (columns[0] / 3 == columns[1] / 3) {
+					continue;
+				}
+
+				let corners = [
+					Vec2::new(columns[0], rows[0]),
+					Vec2::new(columns[1], rows[0]),
+					Vec2::new(columns[0], rows[1]),
+					Vec2::new(columns[1], rows[1]),
+				];
+
+				if corners.iter().any(|point| self.grid.has_number(point)) {
+					continue;
+				}
+
+				let common = corners.iter().fold(!Candidates::new(), |common, point| {
+					common & self.grid.get_candidates(point)
+				});
+
+				for pair in combinations(&common.iter().collect::<Vec<_>>(), 2) {
+					let digits = pair.iter().copied().collect::<Candidates>();
+					steps.extend(self.unique_rectangle(&corners, digits));
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	fn unique_rectangle(&self, corners: &[Vec2; 4], digits: Candidates) -> Vec<Step> {
+		let mut steps = vec![];
+
+		let step = |kind: usize, eliminations: Vec<(Vec2, CellValue)>| Step {
+			cells: corners.to_vec(),
+			digits,
+			eliminations,
+			technique: UNIQUE_RECTANGLES[kind - 1],
+			..Default::default()
+		};
+
+		let roof = corners
+			.iter()
+			.filter(|point| self.grid.get_candidates(point) != digits)
+			.copied()
+			.collect::<Vec<_>>();
+		let extras = roof.iter().fold(Candidates::new(), |extras, point| {
+			extras | (self.grid.get_candidates(point) - digits)
+		});
+
+		if roof.len() == 1 {
+			let eliminations = digits.iter().map(|number| (roof[0], number)).collect();
+			steps.push(step(1, eliminations));
+			return steps;
+		}
+
+		let shares_unit = |cells: &[Vec2]| {
+			cells.len() == 2 && (cells[0].x == cells[1].x || cells[0].y == cells[1].y)
+		};
+
+		// a single extra digit has to be true in one of the cells holding it
+		if let Some(extra) = extras.first()
+			&& extras.len() == 1
+		{
+			let kind = if shares_unit(&roof) { 2 } else { 5 };
+			steps.push(step(kind, self.eliminations_seen_by(&roof, extra)));
+		}
+
+		if roof.len() != 2 {
+			return steps;
+		}
+
+		for unit in Unit::all().filter(|unit| unit.contains(&roof[0]) && unit.contains(&roof[1])) {
+			// the roof acts as one cell holding the extras, which can form a naked subset
+			let others = unit
+				.coords()
+				.into_iter()
+				.filter(|point| !roof.contains(point) && !self.grid.has_number(point))
+				.collect::<Vec<_>>();
+
+			for size in 1..=3 {
+				for subset in combinations(&others, size) {
+					let subset_digits = subset.iter().fold(extras, |subset_digits, point| {
+						subset_digits | self.grid.get_candidates(point)
+					});
+
+					if subset_digits.len() != size + 1 {
+						continue;
+					}
+
+					let mut eliminations = vec![];
+					for point in others.iter().filter(|point| !subset.contains(point)) {
+						for number in (self.grid.get_candidates(point) & subset_digits).iter() {
+							eliminations.push((*point, number));
+						}
+					}
+
+					steps.push(step(3, eliminations));
+				}
+			}
+
+			// when one digit is locked into the roof the other can't appear there
+			for number in digits.iter() {
+				let positions = unit
+					.coords()
+					.into_iter()
+					.filter(|point| self.grid.get_candidates(point).contains(number))
+					.count();
+
+				if positions == 2 {
+					let other = (digits - [number].into_iter().collect()).first().unwrap();
+					steps.push(step(4, roof.iter().map(|point| (*point, other)).collect()));
+				}
+			}
+		}
+
+		if !shares_unit(&roof) {
+			for number in digits.iter() {
+				let locked = |unit: fn(Coord) -> Unit, ranks: [Coord; 2]| {
+					ranks.iter().all(|rank| {
+						unit(*rank)
+							.coords()
+							.iter()
+							.filter(|point| self.grid.get_candidates(point).contains(number))
+							.all(|point| corners.contains(point))
+					})
+				};
+
+				let rows = [corners[0].y, corners[3].y];
+				let columns = [corners[0].x, corners[3].x];
+				if locked(Unit::Row, rows) || locked(Unit::Column, columns) {
+					steps.push(step(6, roof.iter().map(|point| (*point, number)).collect()));
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn bug_plus_one(&self) -> Vec<Step> {
+		let empty = SUDOKU
+			.iter()
+			.filter(|point| !self.grid.has_number(point))
+			.copied()
+			.collect::<Vec<_>>();
+
+		let extra = empty
+			.iter()
+			.filter(|point| self.grid.get_candidates(point).len() != 2)
+			.copied()
+			.collect::<Vec<_>>();
+
+		if extra.len() != 1 || self.grid.get_candidates(&extra[0]).len() != 3 {
+			return vec![];
+		}
+
+		let point = extra[0];
+		let candidates = self.grid.get_candidates(&point);
+
+		// the digit that would otherwise appear three times in the cell's row is the one to keep
+		let row = Unit::Row(point.y);
+		let Some(number) = candidates.iter().find(|number| {
+			row.coords()
+				.iter()
+				.filter(|other| self.grid.get_candidates(other).contains(*number))
+				.count() == 3
+		}) else {
+			return vec![];
+		};
+
+		// without the extra digit every candidate has to appear exactly twice in each unit
+		for unit in Unit::all() {
+			for other in 1..=9 {
+				let mut count = unit
+					.coords()
+					.iter()
+					.filter(|cell| self.grid.get_candidates(cell).contains(other))
+					.count();
+
+				if other == number && unit.contains(&point) {
+					count -= 1;
+				}
+
+				if count != 0 && count != 2 {
+					return vec![];
+				}
+			}
+		}
+
+		return vec![Step {
+			cells: vec![point],
+			digits: [number].into_iter().collect(),
+			eliminations: (candidates - [number].into_iter().collect())
+				.iter()
+				.map(|other| (point, other))
+				.collect(),
+			units: vec![row],
+			..Default::default()
+		}];
+	}
+
+	pub fn almost_locked_sets(&self) -> &[AlmostLockedSet] {
+		self.almost_locked_sets
+			.get_or_init(|| self.find_almost_locked_sets())
+	}
+
+	fn find_almost_locked_sets(&self) -> Vec<AlmostLockedSet> {
+		let mut sets: Vec<AlmostLockedSet> = vec![];
+		// a set found through a row is found again through its square, one bit per cell
+		let mut found: HashSet<u128> = HashSet::new();
+
+		for unit in Unit::all() {
+			let empty = unit
+				.coords()
+				.into_iter()
+				.filter(|point| !self.grid.has_number(point))
+				.collect::<Vec<_>>();
+
+			for size in 1..empty.len() {
+				for cells in combinations(&empty, size) {
+					let digits = cells.iter().fold(Candidates::new(), |digits, point| {
+						digits | self.grid.get_candidates(point)
+					});
+
+					let mask = cells
+						.iter()
+						.fold(0u128, |mask, point| mask | Analysis::cell_bit(point));
+
+					if digits.len() != size + 1 || !found.insert(mask) {
+						continue;
+					}
+
+					sets.push(AlmostLockedSet {
+						cells,
+						digits,
+						unit,
+					});
+				}
+			}
+		}
+
+		return sets;
+	}
+
+	fn cells_with(&self, set: &AlmostLockedSet, number: CellValue) -> Vec<Vec2> {
+		set.cells
+			.iter()
+			.filter(|point| self.grid.get_candidates(point).contains(number))
+			.copied()
+			.collect()
+	}
+
+	// digits that can only be true in one of the two sets, because every cell holding it in
+	// one set sees every cell holding it in the other
+	fn restricted_commons(&self, first: &AlmostLockedSet, second: &AlmostLockedSet) -> Candidates {
+		(first.digits & second.digits)
+			.iter()
+			.filter(|number| {
+				let others = self.cells_with(second, *number);
+				self.cells_with(first, *number)
+					.iter()
+					.all(|point| others.iter().all(|other| Grid::sees(point, other)))
+			})
+			.collect()
+	}
+
+	fn set_eliminations(
+		&self,
+		sets: &[&AlmostLockedSet],
+		number: CellValue,
+	) -> Vec<(Vec2, CellValue)> {
+		let cells = sets
+			.iter()
+			.flat_map(|set| self.cells_with(set, number))
+			.collect::<Vec<_>>();
+
+		let eliminations = self.eliminations_seen_by(&cells, number);
+		return eliminations
+			.into_iter()
+			.filter(|(point, _)| !sets.iter().any(|set| set.cells.contains(point)))
+			.collect();
+	}
+
+	pub fn als_xz(&self) -> Vec<Step> {
+		let sets = self.almost_locked_sets();
+		let mut steps = vec![];
+		let mut eliminated: HashSet<Node> = HashSet::new();
+
+		for (index, first) in sets.iter().enumerate() {
+			for second in sets.iter().skip(index + 1) {
+				if first.overlaps(second) {
+					continue;
+				}
+
+				let restricted = self.restricted_commons(first, second);
+				if restricted.is_empty() {
+					continue;
+				}
+
+				let mut eliminations = vec![];
+				for number in ((first.digits & second.digits) - restricted).iter() {
+					eliminations.extend(self.set_eliminations(&[first, second], number));
+				}
+
+				// doubly linked, each set is locked apart from the restricted commons which are
+				// then true in exactly one of the two sets
+				if restricted.len() == 2 {
+					for number in restricted.iter() {
+						eliminations.extend(self.set_eliminations(&[first, second], number));
+					}
+
+					for set in [first, second] {
+						for number in (set.digits - restricted).iter() {
+							eliminations.extend(self.set_eliminations(&[set], number));
+						}
+					}
+				}
+
+				if !eliminations.iter().any(|other| !eliminated.contains(other)) {
+					continue;
+				}
+
+				eliminated.extend(eliminations.iter().copied());
+
+				steps.push(Step {
+					digits: restricted,
+					eliminations,
+					sets: vec![first.clone(), second.clone()],
+					technique: "als-xz",
+					..Default::default()
+				});
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn als_xy_wings(&self) -> Vec<Step> {
+		let sets = self.almost_locked_sets();
+		let mut steps = vec![];
+		let mut eliminated: HashSet<Node> = HashSet::new();
+
+		for pivot in sets.iter() {
+			let wings = sets
+				.iter()
+				.filter(|set| !set.overlaps(pivot))
+				.map(|set| (set, self.restricted_commons(pivot, set)))
+				.filter(|(_, restricted)| !restricted.is_empty())
+				.collect::<Vec<_>>();
+
+			for (index, (first, first_restricted)) in wings.iter().enumerate() {
+				for (second, second_restricted) in wings.iter().skip(index + 1) {
+					if first.overlaps(second) {
+						continue;
+					}
+
+					let linked = first_restricted
+						.iter()
+						.any(|x| second_restricted.iter().any(|y| x != y));
+					if !linked {
+						continue;
+					}
+
+					let mut eliminations = vec![];
+					for number in (first.digits & second.digits).iter() {
+						// the restricted commons used for the links can't also be the eliminated digit
+						let usable = first_restricted.iter().any(|x| {
+							second_restricted
+								.iter()
+								.any(|y| x != y && x != number && y != number)
+						});
+
+						if usable {
+							eliminations.extend(self.set_eliminations(&[first, second], number));
+						}
+					}
+
+					if !eliminations.iter().any(|other| !eliminated.contains(other)) {
+						continue;
+					}
+
+					eliminated.extend(eliminations.iter().copied());
+
+					steps.push(Step {
+						digits: *first_restricted | *second_restricted,
+						eliminations,
+						sets: vec![(*first).clone(), pivot.clone(), (*second).clone()],
+						technique: "als-xy-wing",
+						..Default::default()
+					});
+				}
+			}
+		}
+
+		return steps;
+	}
+
+	pub fn death_blossoms(&self) -> Vec<Step> {
+		let sets = self.almost_locked_sets();
+		let mut steps = vec![];
+		let mut eliminated: HashSet<Node> = HashSet::new();
+
+		for stem in SUDOKU.iter() {
+			let stem_candidates = self.grid.get_candidates(stem);
+			if stem_candidates.len() < 2 || stem_candidates.len() > 3 {
+				continue;
+			}
+
+			for number in (!stem_candidates).iter() {
+				// one petal per stem candidate, each seeing the stem on that candidate
+				let petals = stem_candidates
+					.iter()
+					.map(|candidate| {
+						sets.iter()
+							.filter(|set| {
+								set.digits.contains(candidate) && set.digits.contains(number)
+							})
+							.filter(|set| !set.cells.contains(stem))
+							.filter(|set| {
+								self.cells_with(set, candidate)
+									.iter()
+									.all(|point| Grid::sees(point, stem))
+							})
+							.collect::<Vec<_>>()
+					})
+					.collect::<Vec<_>>();
+
+				let mut chosen = vec![];
+				self.blossom(
+					stem,
+					number,
+					&petals,
+					&mut chosen,
+					&mut eliminated,
+					&mut steps,
+				);
+			}
+		}
+
+		return steps;
+	}
+
+	fn blossom<'s>(
+		&self,
+		stem: &Vec2,
+		number: CellValue,
+		petals: &[Vec<&'s AlmostLockedSet>],
+		chosen: &mut Vec<&'s AlmostLockedSet>,
+		eliminated: &mut HashSet<Node>,
+		steps: &mut Vec<Step>,
+	) {
+		if chosen.len() == petals.len() {
+			let eliminations = self
+				.set_eliminations(chosen, number)
+				.into_iter()
+				.filter(|(point, _)| point != stem)
+				.collect::<Vec<_>>();
+
+			if eliminations.iter().any(|other| !eliminated.contains(other)) {
+				eliminated.extend(eliminations.iter().copied());
+
+				steps.push(Step {
+					cells: vec![*stem],
+					digits: [number].into_iter().collect(),
+					eliminations,
+					sets: chosen.iter().map(|set| (*set).clone()).collect(),
+					technique: "death blossom",
+					..Default::default()
+				});
+			}
+
+			return;
+		}
+
+		for petal in petals[chosen.len()].iter() {
+			if chosen.iter().any(|other| other.overlaps(petal)) {
+				continue;
+			}
+
+			chosen.push(petal);
+			self.blossom(stem, number, petals, chosen, eliminated, steps);
+			chosen.pop();
+		}
+	}
+
+	pub fn cell_forcing(&self, net: bool) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for point in SUDOKU.iter() {
+			let candidates = self.grid.get_candidates(point);
+			if candidates.len() < 2 || candidates.len() > 3 {
+				continue;
+			}
+
+			let branches = candidates
+				.iter()
+				.map(|number| (*point, number))
+				.collect::<Vec<_>>();
+			let eliminations = self.force(&branches, net);
+			if eliminations.len() == 0 {
+				continue;
+			}
+
+			steps.push(Step {
+				cells: vec![*point],
+				digits: candidates,
+				eliminations,
+				..Default::default()
+			});
+		}
+
+		return steps;
+	}
+
+	pub fn unit_forcing(&self, net: bool) -> Vec<Step> {
+		let mut steps = vec![];
+
+		for unit in Unit::all() {
+			for number in 1..=9 {
+				let positions = unit
+					.coords()
+					.into_iter()
+					.filter(|point| self.grid.get_candidates(point).contains(number))
+					.collect::<Vec<_>>();
+
+				if positions.len() < 2 || positions.len() > 3 {
+					continue;
+				}
+
+				let branches = positions
+					.iter()
+					.map(|point| (*point, number))
+					.collect::<Vec<_>>();
+				let eliminations = self.force(&branches, net);
+				if eliminations.len() == 0 {
+					continue;
+				}
+
+				steps.push(Step {
+					cells: positions,
+					digits: [number].into_iter().collect(),
+					eliminations,
+					units: vec![unit],
+					..Default::default()
+				});
+			}
+		}
+
+		return steps;
+	}
+
+	// one of the branches has to be true, so whatever every surviving branch agrees on holds and
+	// any branch that runs into a contradiction is false
+	fn force(&self, branches: &[(Vec2, CellValue)], net: bool) -> Vec<(Vec2, CellValue)> {
+		let mut outcomes = vec![];
+		let mut eliminations = vec![];
+
+		for (point, number) in branches.iter() {
+			let mut grid = self.grid.clone();
+			grid.insert_number(point, *number);
+
+			if Analysis::propagate(&mut grid, net) {
+				outcomes.push(grid);
+			} else {
+				eliminations.push((*point, *number));
+			}
+		}
+
+		if outcomes.len() == 0 {
+			return vec![];
+		}
+
+		for point in SUDOKU.iter() {
+			for number in self.grid.get_candidates(point).iter() {
+				let removed = outcomes.iter().all(|grid| {
+					grid.get_number(point) != number && !grid.get_candidates(point).contains(number)
+				});
+
+				if removed && !eliminations.contains(&(*point, number)) {
+					eliminations.push((*point, number));
+				}
+			}
+		}
+
+		return eliminations;
+	}
+
+	// fills in singles until nothing changes, a net also keeps going with the cheaper
+	// elimination techniques. returns false once the grid contradicts itself
+	fn propagate(grid: &mut Grid, net: bool) -> bool {
+		loop {
+			if !grid.verify() {
+				return false;
+			}
+
+			let mut changed = false;
+
+			for point in SUDOKU.iter() {
+				let candidates = grid.get_candidates(point);
+				if let Some(number) = candidates.first()
+					&& candidates.len() == 1
+					&& !grid.has_number(point)
+				{
+					grid.insert_number(point, number);
+					changed = true;
+				}
+			}
+
+			for unit in Unit::all() {
+				for number in 1..=9 {
+					let coords = unit.coords();
+					if coords.iter().any(|point| grid.get_number(point) == number) {
+						continue;
+					}
+
+					let positions = coords
+						.iter()
+						.filter(|point| grid.get_candidates(point).contains(number))
+						.collect::<Vec<_>>();
+
+					match positions.len() {
+						0 => return false,
+						1 => {
+							grid.insert_number(positions[0], number);
+							changed = true;
+						}
+						_ => {}
+					}
+				}
+			}
+
+			if !changed && net {
+				let analysis = Analysis::new(grid, false);
+				let steps = [
+					analysis.single_line_in_squares(),
+					analysis.square_claim(),
+					analysis.naked_subsets(),
+					analysis.hidden_subsets(),
+				]
+				.concat();
+
+				for step in steps {
+					for (point, number) in step.eliminations {
+						changed |= analysis.grid.remove_candidate(&point, number);
+					}
+				}
+			}
+
+			if !changed {
+				return true;
+			}
+		}
+	}
+
+	// fills in the rest of the grid from the first solution a plain backtracking search finds,
+	// as one step of the round that made the guess
+	pub fn cheat(&mut self) -> usize {
+		let mut count = 0;
+		let mut found = vec![];
+		Analysis::backtrack(self.grid.clone(), 1, &mut count, &mut found);
+
+		let Some(solved) = found.pop() else {
+			return 0;
+		};
+
+		let placements = SUDOKU
+			.iter()
+			.filter(|point| !self.grid.has_number(point))
+			.map(|point| (*point, solved.get_number(point)))
+			.collect();
+
+		let step = Step {
+			placements,
+			..Default::default()
+		};
+
+		return self.apply_steps("cheat", vec![step]);
+	}
+
+	// singles and then a guess on the cell with the fewest candidates, never any other technique
+	fn backtrack(mut grid: Grid, limit: usize, count: &mut usize, found: &mut Vec<Grid>) {
+		if !Analysis::propagate(&mut grid, false) {
+			return;
+		}
+
+		if grid.is_solved() {
+			*count += 1;
+			if found.len() < 2 {
+				found.push(grid);
+			}
+
+			return;
+		}
+
+		let Some(point) = Analysis::guess_point(&grid) else {
+			return;
+		};
+
+		for candidate in grid.get_candidates(&point) {
+			if *count >= limit {
+				return;
+			}
+
+			let mut guess = grid.clone();
+			guess.insert_number(&point, candidate);
+			Analysis::backtrack(guess, limit, count, found);
+		}
+	}
+
+	// the limit is raised to two so that a unique solution can always be told apart from several
+	pub fn count_solutions(&self, limit: usize) -> Solutions {
+		let mut count = 0;
+		let mut found = vec![];
+		Analysis::backtrack(self.grid.clone(), limit.max(2), &mut count, &mut found);
+
+		return Solutions::from_found(count, found);
+	}
+
+	fn guess_point(grid: &Grid) -> Option<Vec2> {
+		SUDOKU
+			.iter()
+			.filter(|point| !grid.has_number(point))
+			.min_by_key(|point| grid.get_candidates(point).len())
+			.copied()
+	}
+
+	pub fn draw(&self) -> Result<(), ImageError> {
+		return self.draw_steps(&[]);
+	}
+
+	pub fn draw_steps(&self, steps: &[Step]) -> Result<(), ImageError> {
+		let mut grid_image = GridImage::new(self.grid);
+		grid_image.create_image();
+		grid_image.draw_candidates();
+
+		for step in steps.iter() {
+			grid_image.draw_step(step);
+		}
+
+		return grid_image.save(&self.image_path);
 	}
 }
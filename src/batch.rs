@@ -0,0 +1,363 @@
+use std::{
+	collections::BTreeMap,
+	time::{Duration, Instant},
+};
+
+use crate::{
+	analysis::Analysis, builtin::builtins, grid::Grid, technique::Technique, vec2::SUDOKU,
+};
+
+#[derive(Clone, Debug)]
+pub struct BatchResult {
+	pub error: Option<String>,
+	pub file: String,
+	pub line: usize,
+	pub logic_only: bool,
+	pub matches_solution: Option<bool>,
+	pub rounds: usize,
+	pub solved: bool,
+	pub techniques: BTreeMap<&'static str, usize>,
+	pub time: Duration,
+}
+
+impl BatchResult {
+	pub fn solve(
+		file: &str,
+		line: usize,
+		puzzle: &str,
+		cheating: bool,
+		techniques: &[&'static dyn Technique],
+		unique: bool,
+	) -> Self {
+		let start = Instant::now();
+
+		let mut result = BatchResult {
+			error: None,
+			file: String::from(file),
+			line,
+			logic_only: false,
+			matches_solution: None,
+			rounds: 0,
+			solved: false,
+			techniques: BTreeMap::new(),
+			time: Duration::ZERO,
+		};
+
+		let mut grid = Grid::new();
+		if let Err(error) = grid.parse_line(puzzle) {
+			result.error = Some(error.to_string());
+			result.time = start.elapsed();
+			return result;
+		}
+
+		let mut analysis = Analysis::new(&mut grid, cheating);
+		analysis.techniques = techniques.to_vec();
+		analysis.unique = unique;
+
+		result.rounds = analysis.run();
+		result.techniques = analysis.techniques_used().clone();
+		result.solved = analysis.grid.is_solved();
+		result.logic_only = result.solved && !result.techniques.contains_key("cheat");
+
+		if analysis.grid.solution.len() != 0 {
+			result.matches_solution = Some(SUDOKU.iter().all(|point| {
+				let number = analysis.grid.get_number(point);
+				number == 0 || number == analysis.grid.solution[point]
+			}));
+		}
+
+		result.time = start.elapsed();
+
+		return result;
+	}
+
+	fn techniques_string(&self) -> String {
+		self.techniques
+			.iter()
+			.map(|(technique, count)| format!("{}={}", technique, count))
+			.collect::<Vec<_>>()
+			.join(";")
+	}
+}
+
+#[derive(Clone, Debug)]
+pub struct Batch {
+	pub cheating: bool,
+	pub results: Vec<BatchResult>,
+	pub techniques: Vec<&'static dyn Technique>,
+	pub unique: bool,
+}
+
+impl Batch {
+	pub fn new(cheating: bool) -> Self {
+		Batch {
+			cheating,
+			results: vec![],
+			techniques: builtins(),
+			unique: false,
+		}
+	}
+
+	pub fn load(&mut self, path: &str) -> Result<(), std::io::Error> {
+		let text = std::fs::read_to_string(path)?;
+
+		for (index, line) in text.lines().enumerate() {
+			let line = line.trim();
+			if line.len() == 0 || line.starts_with('#') {
+				continue;
+			}
+
+			self.results.push(BatchResult::solve(
+				path,
+				index + 1,
+				line,
+				self.cheating,
+				&self.techniques,
+				self.unique,
+			));
+		}
+
+		return Ok(());
+	}
+
+	pub fn to_csv(&self) -> String {
+		let mut csv = String::from(
+			"file,line,solved,logic_only,rounds,techniques,matches_solution,time_us,error\n",
+		);
+
+		for result in self.results.iter() {
+			csv.push_str(&format!(
+				"{},{},{},{},{},{},{},{},{}\n",
+				csv_string(&result.file),
+				result.line,
+				result.solved,
+				result.logic_only,
+				result.rounds,
+				csv_string(&result.techniques_string()),
+				result
+					.matches_solution
+					.map(|matches| matches.to_string())
+					.unwrap_or_default(),
+				result.time.as_micros(),
+				csv_string(result.error.as_deref().unwrap_or("")),
+			));
+		}
+
+		return csv;
+	}
+
+	pub fn to_json(&self) -> String {
+		let mut json = String::from("[\n");
+
+		for (index, result) in self.results.iter().enumerate() {
+			let techniques = result
+				.techniques
+				.iter()
+				.map(|(technique, count)| format!("{}: {}", json_string(technique), count))
+				.collect::<Vec<_>>()
+				.join(", ");
+
+			json.push_str(&format!(
+				"\t{{\"file\": {}, \"line\": {}, \"solved\": {}, \"logic_only\": {}, \"rounds\": {}, \"techniques\": {{{}}}, \"matches_solution\": {}, \"time_us\": {}, \"error\": {}}}",
+				json_string(&result.file),
+				result.line,
+				result.solved,
+				result.logic_only,
+				result.rounds,
+				techniques,
+				result
+					.matches_solution
+					.map(|matches| matches.to_string())
+					.unwrap_or(String::from("null")),
+				result.time.as_micros(),
+				result
+					.error
+					.as_deref()
+					.map(json_string)
+					.unwrap_or(String::from("null")),
+			));
+
+			if index + 1 != self.results.len() {
+				json.push(',');
+			}
+
+			json.push('\n');
+		}
+
+		json.push_str("]\n");
+
+		return json;
+	}
+
+	pub fn summary(&self) -> String {
+		let total = self.results.len();
+		let invalid = self
+			.results
+			.iter()
+			.filter(|result| result.error.is_some())
+			.count();
+		let solved = self.results.iter().filter(|result| result.solved).count();
+		let logic_only = self
+			.results
+			.iter()
+			.filter(|result| result.logic_only)
+			.count();
+		let checked = self
+			.results
+			.iter()
+			.filter(|result| result.matches_solution.is_some())
+			.count();
+		let mismatched = self
+			.results
+			.iter()
+			.filter(|result| result.matches_solution == Some(false))
+			.count();
+		let time = self
+			.results
+			.iter()
+			.map(|result| result.time)
+			.sum::<Duration>();
+
+		let mut techniques: BTreeMap<&'static str, usize> = BTreeMap::new();
+		for result in self.results.iter() {
+			for (&technique, &count) in result.techniques.iter() {
+				*techniques.entry(technique).or_default() += count;
+			}
+		}
+
+		let mut summary = format!("puzzles: {}\n", total);
+		summary.push_str(&format!("invalid: {}\n", invalid));
+		summary.push_str(&format!(
+			"solved: {} (logic only: {})\n",
+			solved, logic_only
+		));
+		summary.push_str(&format!(
+			"disagree with solution: {} of {}\n",
+			mismatched, checked
+		));
+		summary.push_str(&format!(
+			"time: {:.3}s total, {:.3}ms mean\n",
+			time.as_secs_f64(),
+			if total == 0 {
+				0.0
+			} else {
+				time.as_secs_f64() * 1000.0 / total as f64
+			}
+		));
+
+		summary.push_str("techniques:\n");
+		for (technique, count) in techniques {
+			summary.push_str(&format!("  {}: {}\n", technique, count));
+		}
+
+		return summary;
+	}
+}
+
+fn csv_string(string: &str) -> String {
+	if string.contains([',', '"', '\n']) {
+		format!("\"{}\"", string.replace('"', "\"\""))
+	} else {
+		String::from(string)
+	}
+}
+
+fn json_string(string: &str) -> String {
+	let mut result = String::from("\"");
+
+	for character in string.chars() {
+		match character {
+			'"' => result.push_str("\\\""),
+			'\\' => result.push_str("\\\\"),
+			'\n' => result.push_str("\\n"),
+			'\t' => result.push_str("\\t"),
+			character if character.is_control() => {
+				result.push_str(&format!("\\u{:04x}", character as u32))
+			}
+			character => result.push(character),
+		}
+	}
+
+	result.push('"');
+
+	return result;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::builtin::builtin;
+
+	const PUZZLE: &str = ".7.5.3.4.3.68.17..1..4.62...8....43...........57.....252.........8..4...9....8..5,872593146346821759195476283289165437613742598457389612521937864738654921964218375";
+
+	const HARD: &str =
+		"8..........36......7..9.2...5...7.......457.....1...3...1....68..85...1..9....4..";
+
+	// singles only, so the technique counts stay put when the default order changes
+	fn batch() -> Batch {
+		let mut batch = Batch::new(false);
+		batch.techniques = ["single in squares", "single in rows", "single in columns"]
+			.iter()
+			.map(|name| builtin(name).unwrap())
+			.collect();
+
+		let file = "my \"puzzles\", 1.txt";
+		for (index, line) in [PUZZLE, HARD, "12x"].iter().enumerate() {
+			let mut result =
+				BatchResult::solve(file, index + 1, line, false, &batch.techniques, false);
+			result.time = Duration::from_micros(250);
+			batch.results.push(result);
+		}
+
+		return batch;
+	}
+
+	#[test]
+	fn writes_csv() {
+		assert_eq!(
+			batch().to_csv(),
+			"file,line,solved,logic_only,rounds,techniques,matches_solution,time_us,error
+\"my \"\"puzzles\"\", 1.txt\",1,true,true,7,single in columns=6;single in rows=6;single in squares=7,true,250,
+\"my \"\"puzzles\"\", 1.txt\",2,false,false,0,,,250,
+\"my \"\"puzzles\"\", 1.txt\",3,false,false,0,,,250,\"unexpected character 'x' at row 1, column 3\"
+"
+		);
+	}
+
+	#[test]
+	fn writes_json() {
+		assert_eq!(
+			batch().to_json(),
+			"[
+	{\"file\": \"my \\\"puzzles\\\", 1.txt\", \"line\": 1, \"solved\": true, \"logic_only\": true, \"rounds\": 7, \"techniques\": {\"single in columns\": 6, \"single in rows\": 6, \"single in squares\": 7}, \"matches_solution\": true, \"time_us\": 250, \"error\": null},
+	{\"file\": \"my \\\"puzzles\\\", 1.txt\", \"line\": 2, \"solved\": false, \"logic_only\": false, \"rounds\": 0, \"techniques\": {}, \"matches_solution\": null, \"time_us\": 250, \"error\": null},
+	{\"file\": \"my \\\"puzzles\\\", 1.txt\", \"line\": 3, \"solved\": false, \"logic_only\": false, \"rounds\": 0, \"techniques\": {}, \"matches_solution\": null, \"time_us\": 250, \"error\": \"unexpected character 'x' at row 1, column 3\"}
+]
+"
+		);
+	}
+
+	#[test]
+	fn writes_summary() {
+		assert_eq!(
+			batch().summary(),
+			"puzzles: 3
+invalid: 1
+solved: 1 (logic only: 1)
+disagree with solution: 0 of 1
+time: 0.001s total, 0.250ms mean
+techniques:
+  single in columns: 6
+  single in rows: 6
+  single in squares: 7
+"
+		);
+	}
+
+	#[test]
+	fn escapes_strings() {
+		assert_eq!(csv_string("plain"), "plain");
+		assert_eq!(csv_string("two\nlines"), "\"two\nlines\"");
+		assert_eq!(json_string("a\\b\tc\nd\u{1}"), "\"a\\\\b\\tc\\nd\\u0001\"");
+	}
+}
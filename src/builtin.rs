@@ -0,0 +1,148 @@
+use crate::{
+	analysis::{Analysis, UNIQUE_RECTANGLES},
+	step::Step,
+	technique::Technique,
+	unit::Unit,
+};
+
+#[derive(Clone, Copy, Debug)]
+pub struct Builtin {
+	assumes_unique: bool,
+	find: fn(&Analysis) -> Vec<Step>,
+	last_resort: bool,
+	name: &'static str,
+	reports: &'static [&'static str],
+}
+
+impl Builtin {
+	const fn new(name: &'static str, find: fn(&Analysis) -> Vec<Step>) -> Self {
+		Builtin {
+			assumes_unique: false,
+			find,
+			last_resort: false,
+			name,
+			reports: &[],
+		}
+	}
+
+	const fn unique_only(mut self) -> Self {
+		self.assumes_unique = true;
+		self
+	}
+
+	const fn when_stuck(mut self) -> Self {
+		self.last_resort = true;
+		self
+	}
+
+	const fn reporting(mut self, reports: &'static [&'static str]) -> Self {
+		self.reports = reports;
+		self
+	}
+}
+
+impl Technique for Builtin {
+	fn name(&self) -> &'static str {
+		self.name
+	}
+
+	fn find(&self, analysis: &Analysis) -> Vec<Step> {
+		(self.find)(analysis)
+	}
+
+	fn assumes_unique(&self) -> bool {
+		self.assumes_unique
+	}
+
+	fn last_resort(&self) -> bool {
+		self.last_resort
+	}
+
+	fn reports(&self) -> &[&'static str] {
+		self.reports
+	}
+}
+
+// the default order, cheapest first. last resorts only run in rounds nothing else made progress
+pub static BUILTINS: &[Builtin] = &[
+	Builtin::new("single in squares", |analysis| {
+		analysis.hidden_singles((0..9).map(Unit::Square))
+	}),
+	Builtin::new("single in rows", |analysis| {
+		analysis.hidden_singles((0..9).map(Unit::Row))
+	}),
+	Builtin::new("single in columns", |analysis| {
+		analysis.hidden_singles((0..9).map(Unit::Column))
+	}),
+	Builtin::new("lonely cells", |analysis| analysis.lonely_cells()),
+	Builtin::new("single line in squares", |analysis| {
+		analysis.single_line_in_squares()
+	}),
+	Builtin::new("square claim", |analysis| analysis.square_claim()),
+	Builtin::new("naked subsets", |analysis| analysis.naked_subsets()),
+	Builtin::new("hidden subsets", |analysis| analysis.hidden_subsets()),
+	Builtin::new("x-wing", |analysis| analysis.fish(2)),
+	Builtin::new("swordfish", |analysis| analysis.fish(3)),
+	Builtin::new("jellyfish", |analysis| analysis.fish(4)),
+	Builtin::new("finned x-wing", |analysis| analysis.finned_fish(2, false)),
+	Builtin::new("sashimi x-wing", |analysis| analysis.finned_fish(2, true)),
+	Builtin::new("finned swordfish", |analysis| {
+		analysis.finned_fish(3, false)
+	}),
+	Builtin::new("sashimi swordfish", |analysis| {
+		analysis.finned_fish(3, true)
+	}),
+	Builtin::new("finned jellyfish", |analysis| {
+		analysis.finned_fish(4, false)
+	}),
+	Builtin::new("sashimi jellyfish", |analysis| {
+		analysis.finned_fish(4, true)
+	}),
+	Builtin::new("xy-wing", |analysis| analysis.xy_wings()),
+	Builtin::new("xyz-wing", |analysis| analysis.xyz_wings()),
+	Builtin::new("w-wing", |analysis| analysis.w_wings()),
+	Builtin::new("skyscraper", |analysis| analysis.skyscrapers()),
+	Builtin::new("two-string kite", |analysis| analysis.two_string_kites()),
+	Builtin::new("empty rectangle", |analysis| analysis.empty_rectangles()),
+	Builtin::new("color wrap", |analysis| analysis.color_wraps()),
+	Builtin::new("color trap", |analysis| analysis.color_traps()),
+	Builtin::new("multi-coloring", |analysis| analysis.multi_coloring()),
+	Builtin::new("unique rectangles", |analysis| analysis.unique_rectangles())
+		.unique_only()
+		.reporting(&UNIQUE_RECTANGLES),
+	Builtin::new("bug+1", |analysis| analysis.bug_plus_one()).unique_only(),
+	Builtin::new("sue de coq", |analysis| analysis.sue_de_coq()),
+	Builtin::new("nishio", |analysis| analysis.nishio()).when_stuck(),
+	Builtin::new("templates", |analysis| analysis.templates()).when_stuck(),
+	Builtin::new("chains", |analysis| analysis.alternating_chains())
+		.when_stuck()
+		.reporting(&["x-chain", "xy-chain", "aic"]),
+	Builtin::new("als-xz", |analysis| analysis.als_xz()).when_stuck(),
+	Builtin::new("als-xy-wing", |analysis| analysis.als_xy_wings()).when_stuck(),
+	Builtin::new("death blossom", |analysis| analysis.death_blossoms()).when_stuck(),
+	Builtin::new("cell forcing chain", |analysis| {
+		analysis.cell_forcing(false)
+	})
+	.when_stuck(),
+	Builtin::new("unit forcing chain", |analysis| {
+		analysis.unit_forcing(false)
+	})
+	.when_stuck(),
+	Builtin::new("cell forcing net", |analysis| analysis.cell_forcing(true)).when_stuck(),
+	Builtin::new("unit forcing net", |analysis| analysis.unit_forcing(true)).when_stuck(),
+];
+
+pub fn builtins() -> Vec<&'static dyn Technique> {
+	BUILTINS
+		.iter()
+		.map(|builtin| builtin as &dyn Technique)
+		.collect()
+}
+
+// also takes the names steps are reported under, picking the technique that finds them
+pub fn builtin(name: &str) -> Option<&'static dyn Technique> {
+	BUILTINS
+		.iter()
+		.find(|builtin| builtin.name == name || builtin.reports.contains(&name))
+		.map(|builtin| builtin as &dyn Technique)
+}
@@ -0,0 +1,229 @@
+use std::{
+	fmt::{Debug, Display},
+	ops::{BitAnd, BitOr, Not, Sub},
+};
+
+use crate::grid::CellValue;
+
+// bit n is set when n is a candidate, so bit 0 is always clear
+#[derive(Clone, Copy, Default, Eq, Hash, PartialEq)]
+pub struct Candidates(u16);
+
+impl Candidates {
+	const ALL: u16 = 0b11_1111_1110;
+
+	pub fn new() -> Self {
+		Candidates(0)
+	}
+
+	pub fn insert(&mut self, number: CellValue) -> bool {
+		let had = self.contains(number);
+		self.0 |= 1 << number;
+		return !had;
+	}
+
+	pub fn remove(&mut self, number: CellValue) -> bool {
+		let had = self.contains(number);
+		self.0 &= !(1 << number);
+		return had;
+	}
+
+	pub fn clear(&mut self) {
+		self.0 = 0;
+	}
+
+	pub fn contains(&self, number: CellValue) -> bool {
+		self.0 & (1 << number) != 0
+	}
+
+	pub fn len(&self) -> usize {
+		self.0.count_ones() as usize
+	}
+
+	pub fn is_empty(&self) -> bool {
+		self.0 == 0
+	}
+
+	pub fn first(&self) -> Option<CellValue> {
+		if self.is_empty() {
+			None
+		} else {
+			Some(self.0.trailing_zeros() as CellValue)
+		}
+	}
+
+	pub fn union(&self, other: Candidates) -> Candidates {
+		Candidates(self.0 | other.0)
+	}
+
+	pub fn intersection(&self, other: Candidates) -> Candidates {
+		Candidates(self.0 & other.0)
+	}
+
+	pub fn difference(&self, other: Candidates) -> Candidates {
+		Candidates(self.0 & !other.0)
+	}
+
+	pub fn iter(&self) -> CandidatesIter {
+		CandidatesIter(self.0)
+	}
+}
+
+pub struct CandidatesIter(u16);
+
+impl Iterator for CandidatesIter {
+	type Item = CellValue;
+
+	fn next(&mut self) -> Option<CellValue> {
+		if self.0 == 0 {
+			return None;
+		}
+
+		let number = self.0.trailing_zeros() as CellValue;
+		self.0 &= self.0 - 1;
+		return Some(number);
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let len = self.0.count_ones() as usize;
+		(len, Some(len))
+	}
+}
+
+impl ExactSizeIterator for CandidatesIter {}
+
+impl IntoIterator for Candidates {
+	type Item = CellValue;
+	type IntoIter = CandidatesIter;
+
+	fn into_iter(self) -> CandidatesIter {
+		self.iter()
+	}
+}
+
+impl FromIterator<CellValue> for Candidates {
+	fn from_iter<T: IntoIterator<Item = CellValue>>(iter: T) -> Self {
+		let mut candidates = Candidates::new();
+		for number in iter {
+			candidates.insert(number);
+		}
+
+		return candidates;
+	}
+}
+
+impl BitOr for Candidates {
+	type Output = Self;
+
+	fn bitor(self, other: Self) -> Self {
+		self.union(other)
+	}
+}
+
+impl BitAnd for Candidates {
+	type Output = Self;
+
+	fn bitand(self, other: Self) -> Self {
+		self.intersection(other)
+	}
+}
+
+impl Sub for Candidates {
+	type Output = Self;
+
+	fn sub(self, other: Self) -> Self {
+		self.difference(other)
+	}
+}
+
+impl Not for Candidates {
+	type Output = Self;
+
+	fn not(self) -> Self {
+		Candidates(!self.0 & Candidates::ALL)
+	}
+}
+
+impl Debug for Candidates {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		f.debug_set().entries(self.iter()).finish()
+	}
+}
+
+impl Display for Candidates {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		for number in self.iter() {
+			write!(f, "{}", number)?;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn candidates(numbers: &[CellValue]) -> Candidates {
+		return numbers.iter().copied().collect();
+	}
+
+	#[test]
+	fn insert_and_remove_report_changes() {
+		let mut candidates = Candidates::new();
+
+		assert!(candidates.insert(4));
+		assert!(!candidates.insert(4));
+		assert!(candidates.contains(4));
+		assert_eq!(candidates.len(), 1);
+
+		assert!(candidates.remove(4));
+		assert!(!candidates.remove(4));
+		assert!(!candidates.contains(4));
+		assert!(candidates.is_empty());
+	}
+
+	#[test]
+	fn not_leaves_bit_zero_clear() {
+		let all = !Candidates::new();
+
+		assert_eq!(all.len(), 9);
+		assert!(!all.contains(0));
+		assert_eq!(all.iter().collect::<Vec<_>>(), (1..=9).collect::<Vec<_>>());
+
+		assert_eq!(!candidates(&[1, 5, 9]), candidates(&[2, 3, 4, 6, 7, 8]));
+		assert_eq!(!all, Candidates::new());
+	}
+
+	#[test]
+	fn first_is_the_smallest() {
+		assert_eq!(Candidates::new().first(), None);
+		assert_eq!(candidates(&[7, 3, 9]).first(), Some(3));
+		assert_eq!(candidates(&[9]).first(), Some(9));
+	}
+
+	#[test]
+	fn iterates_in_ascending_order() {
+		let candidates = candidates(&[8, 2, 5, 1]);
+
+		assert_eq!(candidates.iter().collect::<Vec<_>>(), vec![1, 2, 5, 8]);
+		assert_eq!(candidates.iter().len(), 4);
+		assert_eq!(candidates.to_string(), "1258");
+		assert_eq!(format!("{:?}", candidates), "{1, 2, 5, 8}");
+	}
+
+	#[test]
+	fn operators_match_the_set_methods() {
+		let left = candidates(&[1, 2, 3, 4]);
+		let right = candidates(&[3, 4, 5]);
+
+		assert_eq!(left | right, candidates(&[1, 2, 3, 4, 5]));
+		assert_eq!(left & right, candidates(&[3, 4]));
+		assert_eq!(left - right, candidates(&[1, 2]));
+		assert_eq!(right - left, candidates(&[5]));
+
+		assert_eq!(left | right, left.union(right));
+		assert_eq!(left & right, left.intersection(right));
+		assert_eq!(left - right, left.difference(right));
+	}
+}
@@ -0,0 +1,121 @@
+use std::fmt::Display;
+
+use crate::{grid::CellValue, vec2::Vec2};
+
+pub type Node = (Vec2, CellValue);
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Link {
+	Strong,
+	Weak,
+}
+
+impl Link {
+	pub fn opposite(&self) -> Link {
+		match self {
+			Link::Strong => Link::Weak,
+			Link::Weak => Link::Strong,
+		}
+	}
+}
+
+// nodes[i] and nodes[i + 1] are joined by links[i]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Chain {
+	pub links: Vec<Link>,
+	pub nodes: Vec<Node>,
+}
+
+impl Chain {
+	pub fn kind(&self) -> &'static str {
+		let number = self.nodes[0].1;
+		if self.nodes.iter().all(|(_, other)| *other == number) {
+			return "x-chain";
+		}
+
+		let bivalue = self.links.iter().enumerate().all(|(index, link)| {
+			let (from, to) = (self.nodes[index], self.nodes[index + 1]);
+			match link {
+				Link::Strong => from.0 == to.0,
+				Link::Weak => from.0 != to.0 && from.1 == to.1,
+			}
+		});
+
+		if bivalue {
+			return "xy-chain";
+		}
+
+		return "aic";
+	}
+}
+
+impl Display for Chain {
+	// eureka notation, so a strong link inside one cell reads (a=b)r1c1
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		let mut index = 0;
+		while index < self.nodes.len() {
+			let (point, number) = self.nodes[index];
+
+			if self.links.get(index) == Some(&Link::Strong) && self.nodes[index + 1].0 == point {
+				let other = self.nodes[index + 1].1;
+				write!(f, "({}={})r{}c{}", number, other, point.y + 1, point.x + 1)?;
+				index += 1;
+			} else {
+				write!(f, "({})r{}c{}", number, point.y + 1, point.x + 1)?;
+			}
+
+			match self.links.get(index) {
+				Some(Link::Strong) => write!(f, "=")?,
+				Some(Link::Weak) => write!(f, "-")?,
+				None => {}
+			}
+
+			index += 1;
+		}
+
+		Ok(())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn merges_strong_links_inside_a_cell() {
+		let chain = Chain {
+			links: vec![
+				Link::Strong,
+				Link::Weak,
+				Link::Strong,
+				Link::Weak,
+				Link::Strong,
+			],
+			nodes: vec![
+				(Vec2::new(0, 0), 1),
+				(Vec2::new(0, 0), 2),
+				(Vec2::new(4, 0), 2),
+				(Vec2::new(4, 0), 3),
+				(Vec2::new(4, 4), 3),
+				(Vec2::new(4, 4), 1),
+			],
+		};
+
+		assert_eq!(chain.to_string(), "(1=2)r1c1-(2=3)r1c5-(3=1)r5c5");
+	}
+
+	#[test]
+	fn keeps_links_between_cells_apart() {
+		let chain = Chain {
+			links: vec![Link::Strong, Link::Weak, Link::Strong],
+			nodes: vec![
+				(Vec2::new(0, 0), 5),
+				(Vec2::new(0, 6), 5),
+				(Vec2::new(8, 6), 5),
+				(Vec2::new(8, 2), 5),
+			],
+		};
+
+		assert_eq!(chain.to_string(), "(5)r1c1=(5)r7c1-(5)r7c9=(5)r3c9");
+	}
+}
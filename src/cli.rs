@@ -0,0 +1,282 @@
+use std::path::Path;
+
+use sudoku_solver::{builtin, builtins, Stepping, Technique};
+
+const USAGE: &str = "\
+usage: sudoku-solver <command> [options] <puzzle>...
+
+puzzles are either nine rows of digits and '_' optionally followed by a blank line and the
+solution, or a single line of 81 digits with '.' or '0' for blanks optionally followed by the
+solution
+
+commands:
+  solve     solve the puzzle(s), draw the result and print it as an 81-character line
+  render    draw the puzzle(s) with their starting candidates
+  check     verify that the puzzle(s) and their solutions are consistent
+  rate      report how far logic alone gets on the puzzle(s)
+  batch     solve every line of the given collection(s) and report on each puzzle
+
+options:
+  -o, --output <path>  image to write, suffixed per puzzle when given several (default: sudoku.png)
+                       for batch, the report to write as .json or .csv (default: csv to stdout)
+  -s, --step <mode>    draw and wait for enter after every `round` or every `technique`, or
+                       draw without waiting every <n> rounds
+  -c, --cheat          guess when logic stalls
+  -u, --unique         assume the puzzle(s) have a single solution and use techniques that rely
+                       on it
+  -t, --techniques <names>
+                       comma separated techniques to use, in the order given
+  -x, --exclude <names>
+                       comma separated techniques to leave out
+  -l, --limit <n>      stop counting solutions during check after <n> (default: 2)
+  -h, --help           print this message";
+
+pub fn usage() -> String {
+	let mut usage = format!("{}\n\ntechniques, in their default order:\n ", USAGE);
+	let names = builtins()
+		.iter()
+		.map(|technique| technique.name())
+		.collect::<Vec<_>>();
+	list(&mut usage, &names, 1);
+
+	usage.push_str("\n\nnames steps are reported under, which pick the technique that finds them:");
+	for technique in builtins()
+		.iter()
+		.filter(|technique| technique.reports().len() != 0)
+	{
+		let indent = format!("\n  {}:", technique.name());
+		usage.push_str(&indent);
+		list(&mut usage, technique.reports(), indent.len() - 1);
+	}
+
+	return usage;
+}
+
+// appends the names comma separated, wrapping lines past 94 columns at the given indent
+fn list(usage: &mut String, names: &[&str], indent: usize) {
+	let mut width = indent;
+	for (index, name) in names.iter().enumerate() {
+		if index != 0 {
+			usage.push(',');
+			width += 1;
+		}
+
+		if width + name.len() + 1 > 94 {
+			usage.push('\n');
+			usage.push_str(&" ".repeat(indent));
+			width = indent;
+		}
+
+		usage.push(' ');
+		usage.push_str(name);
+		width += name.len() + 1;
+	}
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Mode {
+	Solve,
+	Render,
+	Check,
+	Rate,
+	Batch,
+}
+
+#[derive(Clone, Debug)]
+pub struct Cli {
+	pub cheating: bool,
+	pub inputs: Vec<String>,
+	pub limit: usize,
+	pub mode: Mode,
+	pub output: Option<String>,
+	pub stepping: Stepping,
+	pub techniques: Vec<&'static dyn Technique>,
+	pub unique: bool,
+}
+
+impl Cli {
+	pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+		let mode = match args.next().as_deref() {
+			Some("solve") => Mode::Solve,
+			Some("render") => Mode::Render,
+			Some("check") => Mode::Check,
+			Some("rate") => Mode::Rate,
+			Some("batch") => Mode::Batch,
+			Some("-h") | Some("--help") => return Err(String::new()),
+			Some(command) => return Err(format!("unknown command '{}'", command)),
+			None => return Err(String::from("no command given")),
+		};
+
+		let mut cli = Cli {
+			cheating: false,
+			inputs: vec![],
+			limit: 2,
+			mode,
+			output: None,
+			stepping: Stepping::Off,
+			techniques: builtins(),
+			unique: false,
+		};
+
+		let mut excluded = vec![];
+
+		while let Some(arg) = args.next() {
+			match arg.as_str() {
+				"-o" | "--output" => {
+					let Some(output) = args.next() else {
+						return Err(format!("{} expects a path", arg));
+					};
+
+					cli.output = Some(output);
+				}
+				"-s" | "--step" => {
+					let Some(mode) = args.next() else {
+						return Err(format!("{} expects a mode", arg));
+					};
+
+					cli.stepping = match mode.as_str() {
+						"off" => Stepping::Off,
+						"round" => Stepping::PerRound,
+						"technique" => Stepping::PerTechnique,
+						rounds => match rounds.parse::<usize>() {
+							Ok(rounds) if rounds > 0 => Stepping::RenderEvery(rounds),
+							_ => return Err(format!("unknown step mode '{}'", mode)),
+						},
+					};
+				}
+				"-c" | "--cheat" => cli.cheating = true,
+				"-u" | "--unique" => cli.unique = true,
+				"-t" | "--techniques" => {
+					let Some(names) = args.next() else {
+						return Err(format!("{} expects a list of techniques", arg));
+					};
+
+					cli.techniques = techniques(&names)?;
+				}
+				"-x" | "--exclude" => {
+					let Some(names) = args.next() else {
+						return Err(format!("{} expects a list of techniques", arg));
+					};
+
+					excluded.extend(techniques(&names)?);
+				}
+				"-l" | "--limit" => {
+					cli.limit = match args.next().map(|limit| limit.parse::<usize>()) {
+						Some(Ok(limit)) if limit >= 2 => limit,
+						_ => return Err(format!("{} expects a number of at least 2", arg)),
+					};
+				}
+				"-h" | "--help" => return Err(String::new()),
+				_ if arg.starts_with('-') => return Err(format!("unknown option '{}'", arg)),
+				_ => cli.inputs.push(arg),
+			}
+		}
+
+		if cli.inputs.len() == 0 {
+			return Err(String::from("no puzzle given"));
+		}
+
+		cli.techniques.retain(|technique| {
+			!excluded
+				.iter()
+				.any(|other| other.name() == technique.name())
+		});
+
+		return Ok(cli);
+	}
+
+	/// Image path for the puzzle at `index`. With several puzzles the puzzle's file stem is
+	/// appended to the output stem so runs don't overwrite each other's images.
+	pub fn output_path(&self, index: usize) -> String {
+		let output = self.output.as_deref().unwrap_or("sudoku.png");
+		if self.inputs.len() == 1 {
+			return String::from(output);
+		}
+
+		let output = Path::new(output);
+		let stem = output
+			.file_stem()
+			.and_then(|stem| stem.to_str())
+			.unwrap_or("sudoku");
+		let extension = output
+			.extension()
+			.and_then(|extension| extension.to_str())
+			.unwrap_or("png");
+		let input_stem = Path::new(&self.inputs[index])
+			.file_stem()
+			.and_then(|stem| stem.to_str())
+			.unwrap_or("puzzle");
+
+		return output
+			.with_file_name(format!("{}-{}.{}", stem, input_stem, extension))
+			.to_string_lossy()
+			.into_owned();
+	}
+}
+
+fn techniques(names: &str) -> Result<Vec<&'static dyn Technique>, String> {
+	names
+		.split(',')
+		.map(|name| name.trim())
+		.filter(|name| name.len() != 0)
+		.map(|name| builtin(name).ok_or_else(|| format!("unknown technique '{}'", name)))
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	fn parse(args: &[&str]) -> Result<Cli, String> {
+		return Cli::parse(args.iter().map(|arg| String::from(*arg)));
+	}
+
+	fn names(cli: &Cli) -> Vec<&'static str> {
+		return cli
+			.techniques
+			.iter()
+			.map(|technique| technique.name())
+			.collect();
+	}
+
+	#[test]
+	fn uses_the_techniques_in_the_order_given() {
+		let cli = parse(&["rate", "-t", "chains, x-wing,,single in rows", "one.txt"]).unwrap();
+
+		assert_eq!(names(&cli), vec!["chains", "x-wing", "single in rows"]);
+	}
+
+	#[test]
+	fn excludes_techniques_from_the_default_order() {
+		let cli = parse(&["rate", "-x", "als-xz,death blossom", "one.txt"]).unwrap();
+
+		assert_eq!(names(&cli).len(), builtins().len() - 2);
+		assert!(!names(&cli).contains(&"als-xz"));
+		assert!(!names(&cli).contains(&"death blossom"));
+		assert!(names(&cli).contains(&"als-xy-wing"));
+
+		// excluding applies after -t, wherever it comes on the line
+		let cli = parse(&["rate", "-x", "x-wing", "-t", "x-wing,swordfish", "one.txt"]).unwrap();
+
+		assert_eq!(names(&cli), vec!["swordfish"]);
+	}
+
+	#[test]
+	fn accepts_the_names_steps_are_reported_under() {
+		let cli = parse(&["rate", "-t", "xy-chain,unique rectangle type 5", "one.txt"]).unwrap();
+
+		assert_eq!(names(&cli), vec!["chains", "unique rectangles"]);
+	}
+
+	#[test]
+	fn rejects_unknown_techniques() {
+		assert_eq!(
+			parse(&["rate", "-x", "x-wing,y-wing", "one.txt"]).err(),
+			Some(String::from("unknown technique 'y-wing'"))
+		);
+		assert_eq!(
+			parse(&["rate", "-t"]).err(),
+			Some(String::from("-t expects a list of techniques"))
+		);
+	}
+}
@@ -0,0 +1,29 @@
+pub fn combinations<T: Copy>(items: &[T], size: usize) -> Vec<Vec<T>> {
+	let mut result = vec![];
+	let mut current = vec![];
+	combine(items, size, 0, &mut current, &mut result);
+	return result;
+}
+
+fn combine<T: Copy>(
+	items: &[T],
+	size: usize,
+	start: usize,
+	current: &mut Vec<T>,
+	result: &mut Vec<Vec<T>>,
+) {
+	if current.len() == size {
+		result.push(current.clone());
+		return;
+	}
+
+	for index in start..items.len() {
+		if items.len() - index < size - current.len() {
+			break;
+		}
+
+		current.push(items[index]);
+		combine(items, size, index + 1, current, result);
+		current.pop();
+	}
+}
@@ -1,177 +1,324 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+	collections::{HashMap, HashSet},
+	str::FromStr,
+};
 
 use crate::{
+	candidates::Candidates,
 	line::{Line, LineDirection},
+	load_error::LoadError,
 	square::Square,
-	vec2::Vec2,
+	vec2::{Vec2, SUDOKU},
 };
 
 pub type CellValue = u8;
 pub type Coord = u8;
 
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct Grid {
-	pub columns: [Line; 9],
+	candidates: [Candidates; 81],
+	cells: [CellValue; 81],
 	pub original_numbers: HashSet<Vec2>,
-	pub rows: [Line; 9],
-	pub squares: [Square; 9],
 	pub invalid_cells: Vec<Vec2>,
 	pub solution: HashMap<Vec2, CellValue>,
 }
 
+impl Default for Grid {
+	fn default() -> Self {
+		Grid::new()
+	}
+}
+
 impl Grid {
 	pub fn new() -> Self {
-		let mut grid = Grid::default();
-
-		for row in 0..9 {
-			grid.rows[row] = Line::new(Vec2::new(0, row as Coord), LineDirection::Row);
+		Grid {
+			candidates: [Candidates::new(); 81],
+			cells: [0; 81],
+			original_numbers: HashSet::new(),
+			invalid_cells: vec![],
+			solution: HashMap::new(),
 		}
+	}
+
+	fn index(point: &Vec2) -> usize {
+		point.y as usize * 9 + point.x as usize
+	}
+
+	pub fn row(&self, y: Coord) -> Line<'_> {
+		Line::new(self, Vec2::new(0, y), LineDirection::Row)
+	}
+
+	pub fn column(&self, x: Coord) -> Line<'_> {
+		Line::new(self, Vec2::new(x, 0), LineDirection::Column)
+	}
+
+	pub fn square(&self, index: usize) -> Square<'_> {
+		Square::new(self, index as Coord % 3, index as Coord / 3)
+	}
+
+	pub fn squares(&self) -> impl Iterator<Item = Square<'_>> {
+		(0..9).map(|index| self.square(index))
+	}
+
+	pub fn load(&mut self, path: &str) -> Result<(), LoadError> {
+		let text = std::fs::read_to_string(path).map_err(LoadError::Io)?;
+		return self.parse(&text);
+	}
 
-		for column in 0..9 {
-			grid.columns[column] = Line::new(Vec2::new(column as Coord, 0), LineDirection::Column);
+	pub fn parse(&mut self, text: &str) -> Result<(), LoadError> {
+		let mut lines = text
+			.lines()
+			.map(|line| line.trim())
+			.skip_while(|line| line.len() == 0);
+		let puzzle_lines = lines
+			.by_ref()
+			.take_while(|line| line.len() != 0)
+			.collect::<Vec<_>>();
+		let solution_lines = lines.filter(|line| line.len() != 0).collect::<Vec<_>>();
+
+		if puzzle_lines.len() == 1 && solution_lines.len() == 0 {
+			return self.parse_line(puzzle_lines[0]);
 		}
 
-		for x in 0..3 {
-			for y in 0..3 {
-				grid.squares[Square::square_coord_to_index(x, y)] = Square::new(x, y);
+		let numbers = Grid::parse_block(&puzzle_lines, &['_'])?;
+		let solution = if solution_lines.len() == 0 {
+			None
+		} else {
+			Some(
+				Grid::parse_block(&solution_lines, &[])
+					.map_err(|error| LoadError::MalformedSolution(Box::new(error)))?,
+			)
+		};
+
+		return self.fill(&numbers, solution.as_ref());
+	}
+
+	pub fn parse_line(&mut self, line: &str) -> Result<(), LoadError> {
+		let mut parts = line
+			.split([' ', '\t', ',', ':', ';'])
+			.filter(|part| part.len() != 0);
+
+		let numbers = Grid::parse_cells(parts.next().unwrap_or(""), &['.', '0', '_'])?;
+		// anything after the solution, like a rating, is ignored
+		let solution = match parts.next() {
+			Some(solution) => Some(
+				Grid::parse_cells(solution, &[])
+					.map_err(|error| LoadError::MalformedSolution(Box::new(error)))?,
+			),
+			None => None,
+		};
+
+		return self.fill(&numbers, solution.as_ref());
+	}
+
+	pub fn to_line(&self, solution: bool) -> String {
+		let mut line = String::new();
+
+		for y in 0..9 {
+			for x in 0..9 {
+				line.push(match self.get_number(&Vec2::new(x, y)) {
+					0 => '.',
+					number => (b'0' + number) as char,
+				});
 			}
 		}
 
-		for x in 0..3 {
-			for y in 0..3 {
-				let square = grid
-					.squares
-					.get_mut(Square::square_coord_to_index(x, y))
-					.expect("Could not get square");
-
-				let mut index = 0;
-				for i in (x * 3)..(x * 3 + 3) {
-					square.rows[index] = i as usize;
-					index += 1;
-				}
+		if solution && self.solution.len() != 0 {
+			line.push(',');
 
-				let mut index = 0;
-				for i in (y * 3)..(y * 3 + 3) {
-					square.columns[index] = i as usize;
-					index += 1;
+			for y in 0..9 {
+				for x in 0..9 {
+					line.push((b'0' + self.solution[&Vec2::new(x, y)]) as char);
 				}
 			}
 		}
 
-		return grid;
+		return line;
 	}
 
-	pub fn load(&mut self) {
-		let lines = std::fs::read_to_string("./puzzle1.txt").expect("Could not read puzzle");
-		let lines = lines.split("\n");
-
-		let mut x = 0;
-		let mut y = 0;
+	fn fill(
+		&mut self,
+		numbers: &[[CellValue; 9]; 9],
+		solution: Option<&[[CellValue; 9]; 9]>,
+	) -> Result<(), LoadError> {
+		let conflicts = Grid::conflicts(numbers);
+		if conflicts.len() != 0 {
+			return Err(LoadError::ConflictingGivens(conflicts));
+		}
 
-		let mut load_solution = false;
-		for line in lines {
-			if line.trim().len() == 0 {
-				load_solution = true;
-				x = 0;
-				y = 0;
-				continue;
+		if let Some(solution) = solution {
+			let contradictions = SUDOKU
+				.iter()
+				.filter(|point| {
+					let number = numbers[point.y as usize][point.x as usize];
+					number != 0 && number != solution[point.y as usize][point.x as usize]
+				})
+				.copied()
+				.collect::<Vec<_>>();
+
+			if contradictions.len() != 0 {
+				return Err(LoadError::ContradictingSolution(contradictions));
 			}
+		}
 
-			for char in line.trim().chars() {
-				if char != '_' {
-					let number = char
-						.to_string()
-						.parse::<CellValue>()
-						.expect("Could not parse number");
-
-					if load_solution {
-						self.solution.insert(Vec2::new(x, y), number);
-					} else {
-						self.insert_number(&Vec2::new(x, y), number);
-						self.original_numbers.insert(Vec2::new(x, y));
-					}
-				}
+		for point in SUDOKU.iter() {
+			let number = numbers[point.y as usize][point.x as usize];
+			if number != 0 {
+				self.insert_number(point, number);
+				self.original_numbers.insert(*point);
+			}
 
-				x += 1;
+			if let Some(solution) = solution {
+				self.solution
+					.insert(*point, solution[point.y as usize][point.x as usize]);
 			}
+		}
 
-			x = 0;
-			y += 1;
+		for point in SUDOKU.iter() {
+			if !self.has_number(point) {
+				self.calculate_candidates(point);
+			}
 		}
-	}
 
-	pub fn has_number(&self, point: &Vec2) -> bool {
-		self.rows[point.y as usize].get_number(point) != 0
+		return Ok(());
 	}
 
-	pub fn insert_number(&mut self, point: &Vec2, number: CellValue) {
-		let square = &mut self.squares[Square::square_coord_to_index(point.x / 3, point.y / 3)];
-		square.cells.insert(number);
+	fn parse_cell(
+		character: char,
+		blanks: &[char],
+		row: usize,
+		column: usize,
+	) -> Result<CellValue, LoadError> {
+		match character {
+			'1'..='9' => Ok(character as CellValue - b'0'),
+			_ if blanks.contains(&character) => Ok(0),
+			_ => Err(LoadError::BadCharacter {
+				row,
+				column,
+				character,
+			}),
+		}
+	}
 
-		let row = &mut self.rows[point.y as usize];
-		let column = &mut self.columns[point.x as usize];
+	fn parse_block(lines: &[&str], blanks: &[char]) -> Result<[[CellValue; 9]; 9], LoadError> {
+		if lines.len() != 9 {
+			return Err(LoadError::WrongRowCount { rows: lines.len() });
+		}
 
-		row.set_number(point, number);
-		column.set_number(point, number);
+		let mut numbers = [[0; 9]; 9];
+		for (row, line) in lines.iter().enumerate() {
+			for (column, character) in line.chars().enumerate() {
+				let number = Grid::parse_cell(character, blanks, row, column)?;
+				if column < 9 {
+					numbers[row][column] = number;
+				}
+			}
 
-		row.clear_candidates(point);
-		column.clear_candidates(point);
+			let length = line.chars().count();
+			if length != 9 {
+				return Err(LoadError::WrongRowLength { row, length });
+			}
+		}
 
-		for point in square.coords() {
-			let row = &mut self.rows[point.y as usize];
-			let column = &mut self.columns[point.x as usize];
+		return Ok(numbers);
+	}
 
-			row.remove_candidate(point, number);
-			column.remove_candidate(point, number);
+	fn parse_cells(cells: &str, blanks: &[char]) -> Result<[[CellValue; 9]; 9], LoadError> {
+		let mut numbers = [[0; 9]; 9];
+		for (index, character) in cells.chars().enumerate() {
+			let number = Grid::parse_cell(character, blanks, index / 9, index % 9)?;
+			if index < 81 {
+				numbers[index / 9][index % 9] = number;
+			}
 		}
 
-		for x in 0..9 {
-			self.remove_candidate(&Vec2::new(x, point.y), number);
+		let length = cells.chars().count();
+		if length != 81 {
+			return Err(LoadError::WrongLength { length });
 		}
 
-		for y in 0..9 {
-			self.remove_candidate(&Vec2::new(point.x, y), number);
-		}
+		return Ok(numbers);
 	}
 
-	pub fn verify_data_structure(&self) {
-		for x in 0..9 {
-			for y in 0..9 {
-				let square = &self.squares[Square::square_coord_to_index(x / 3, y / 3)];
-				let number = self.get_number(&Vec2::new(x, y));
-				if number == 0 {
-					continue;
-				}
+	fn conflicts(numbers: &[[CellValue; 9]; 9]) -> Vec<Vec2> {
+		let mut result = vec![];
 
-				assert!(square.cells.contains(&number), "({}, {}): {}", x, y, number);
+		for point in SUDOKU.iter() {
+			let number = numbers[point.y as usize][point.x as usize];
+			if number == 0 {
+				continue;
 			}
+
+			let conflicting = SUDOKU.iter().any(|other| {
+				other != point
+					&& numbers[other.y as usize][other.x as usize] == number
+					&& (other.x == point.x
+						|| other.y == point.y
+						|| Square::point_to_index(other) == Square::point_to_index(point))
+			});
+
+			if conflicting {
+				result.push(*point);
+			}
+		}
+
+		return result;
+	}
+
+	pub fn has_number(&self, point: &Vec2) -> bool {
+		self.get_number(point) != 0
+	}
+
+	pub fn insert_number(&mut self, point: &Vec2, number: CellValue) {
+		self.cells[Grid::index(point)] = number;
+		self.candidates[Grid::index(point)].clear();
+
+		for other in Grid::peers(point) {
+			self.remove_candidate(&other, number);
 		}
 	}
 
+	pub fn sees(a: &Vec2, b: &Vec2) -> bool {
+		a != b
+			&& (a.x == b.x || a.y == b.y || Square::point_to_index(a) == Square::point_to_index(b))
+	}
+
+	pub fn peers(point: &Vec2) -> Vec<Vec2> {
+		SUDOKU
+			.iter()
+			.filter(|other| Grid::sees(point, other))
+			.copied()
+			.collect()
+	}
+
+	pub fn is_solved(&self) -> bool {
+		self.cells.iter().all(|&number| number != 0)
+	}
+
 	pub fn verify(&mut self) -> bool {
 		let mut valid = true;
 
-		for row in self.rows.iter() {
-			if let Some(invalid_point) = row.verify() {
-				self.invalid_cells.push(invalid_point);
-				valid = false;
+		let mut invalid_cells = vec![];
+		for line in self.lines() {
+			if let Some(invalid_point) = line.verify() {
+				invalid_cells.push(invalid_point);
 			}
 		}
 
-		for column in self.columns.iter() {
-			if let Some(invalid_point) = column.verify() {
-				self.invalid_cells.push(invalid_point);
-				valid = false;
+		for square in self.squares() {
+			if let Some(invalid_point) = square.verify() {
+				invalid_cells.push(invalid_point);
 			}
 		}
 
-		for x in 0..9 {
-			for y in 0..9 {
-				if !self.has_number(&Vec2::new(x, y))
-					&& self.get_candidates(&Vec2::new(x, y)).len() == 0
-				{
-					return false;
-				}
+		if invalid_cells.len() != 0 {
+			self.invalid_cells.append(&mut invalid_cells);
+			valid = false;
+		}
+
+		for point in SUDOKU.iter() {
+			if !self.has_number(point) && self.get_candidates(point).len() == 0 {
+				return false;
 			}
 		}
 
@@ -179,67 +326,242 @@ impl Grid {
 	}
 
 	pub fn get_number(&self, point: &Vec2) -> CellValue {
-		let row_number = self.rows[point.y as usize].get_number(point);
-		let column_number = self.columns[point.x as usize].get_number(point);
-		assert!(row_number == column_number);
-		return row_number;
+		self.cells[Grid::index(point)]
 	}
 
-	pub fn set_candidates(&mut self, point: &Vec2, candidates: Vec<CellValue>) {
-		self.rows[point.y as usize].set_candidates(point, candidates.clone());
-		self.columns[point.x as usize].set_candidates(point, candidates);
+	pub fn set_candidates(&mut self, point: &Vec2, candidates: Candidates) {
+		self.candidates[Grid::index(point)] = candidates;
 	}
 
 	pub fn remove_candidate(&mut self, point: &Vec2, number: CellValue) -> bool {
-		self.columns[point.x as usize].remove_candidate(point, number);
-		return self.rows[point.y as usize].remove_candidate(point, number);
+		self.candidates[Grid::index(point)].remove(number)
 	}
 
-	pub fn get_candidates(&self, point: &Vec2) -> &Vec<CellValue> {
-		let row_candidates = self.rows[point.y as usize].get_candidates(point);
-		let column_candidates = self.columns[point.x as usize].get_candidates(point);
-
-		assert!(
-			row_candidates == column_candidates,
-			"{}: {:?} != {:?}",
-			point,
-			row_candidates,
-			column_candidates
-		);
-
-		return row_candidates;
+	pub fn get_candidates(&self, point: &Vec2) -> Candidates {
+		self.candidates[Grid::index(point)]
 	}
 
 	pub fn calculate_candidates(&mut self, point: &Vec2) {
-		let row = &self.rows[point.y as usize];
-		let column = &self.columns[point.x as usize];
-		let square = &self.squares[Square::square_coord_to_index(point.x / 3, point.y / 3)];
+		let row = self.row(point.y);
+		let column = self.column(point.x);
+		let square = self.square(Square::point_to_index(point));
 
-		let mut candidates = vec![];
+		let mut candidates = Candidates::new();
 
 		for candidate in 1..=9 {
 			if !row.has_number(candidate)
 				&& !column.has_number(candidate)
 				&& !square.has_number(candidate)
 			{
-				candidates.push(candidate);
+				candidates.insert(candidate);
 			}
 		}
 
 		self.set_candidates(point, candidates);
 	}
 
-	pub fn lines(&self) -> Vec<&Line> {
+	pub fn lines(&self) -> Vec<Line<'_>> {
 		let mut result = vec![];
 
-		for row in self.rows.iter() {
-			result.push(row);
+		for y in 0..9 {
+			result.push(self.row(y));
 		}
 
-		for column in self.columns.iter() {
-			result.push(column);
+		for x in 0..9 {
+			result.push(self.column(x));
 		}
 
 		return result;
 	}
 }
+
+impl FromStr for Grid {
+	type Err = LoadError;
+
+	fn from_str(text: &str) -> Result<Self, LoadError> {
+		let mut grid = Grid::new();
+		grid.parse(text)?;
+		return Ok(grid);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const PUZZLE: &str = "\
+_7_5_3_4_
+3_68_17__
+1__4_62__
+_8____43_
+_________
+_57_____2
+52_______
+__8__4___
+9____8__5";
+
+	const SOLUTION: &str = "\
+872593146
+346821759
+195476283
+289165437
+613742598
+457389612
+521937864
+738654921
+964218375";
+
+	fn replace_row(text: &str, row: usize, replacement: &str) -> String {
+		return text
+			.lines()
+			.enumerate()
+			.map(|(index, line)| if index == row { replacement } else { line })
+			.collect::<Vec<_>>()
+			.join("\n");
+	}
+
+	#[test]
+	fn parses_a_block_with_its_solution() {
+		let grid = format!("{}\n\n{}", PUZZLE, SOLUTION)
+			.parse::<Grid>()
+			.unwrap();
+
+		assert_eq!(grid.get_number(&Vec2::new(1, 0)), 7);
+		assert_eq!(grid.original_numbers.len(), 26);
+		assert_eq!(grid.solution[&Vec2::new(0, 0)], 8);
+	}
+
+	#[test]
+	fn reports_bad_characters_by_row_and_column() {
+		let text = replace_row(PUZZLE, 3, "_8__x_43_");
+
+		match text.parse::<Grid>() {
+			Err(LoadError::BadCharacter {
+				row: 3,
+				column: 4,
+				character: 'x',
+			}) => {}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	#[test]
+	fn reports_short_and_long_rows() {
+		match replace_row(PUZZLE, 5, "_57_____").parse::<Grid>() {
+			Err(LoadError::WrongRowLength { row: 5, length: 8 }) => {}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+
+		match replace_row(PUZZLE, 0, "_7_5_3_4__").parse::<Grid>() {
+			Err(LoadError::WrongRowLength { row: 0, length: 10 }) => {}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	#[test]
+	fn reports_the_row_count() {
+		let text = PUZZLE.lines().take(8).collect::<Vec<_>>().join("\n");
+
+		match text.parse::<Grid>() {
+			Err(LoadError::WrongRowCount { rows: 8 }) => {}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	#[test]
+	fn wraps_solution_errors() {
+		let solution = replace_row(SOLUTION, 2, "19547628_");
+
+		match format!("{}\n\n{}", PUZZLE, solution).parse::<Grid>() {
+			Err(LoadError::MalformedSolution(error)) => match *error {
+				LoadError::BadCharacter {
+					row: 2,
+					column: 8,
+					character: '_',
+				} => {}
+				other => panic!("unexpected solution error {:?}", other),
+			},
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	#[test]
+	fn reports_every_conflicting_given() {
+		let text = replace_row(PUZZLE, 4, "7________");
+
+		match text.parse::<Grid>() {
+			Err(LoadError::ConflictingGivens(points)) => {
+				assert_eq!(points, vec![Vec2::new(0, 4), Vec2::new(2, 5)]);
+			}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	#[test]
+	fn reports_solutions_that_contradict_givens() {
+		let solution = replace_row(SOLUTION, 0, "827593146");
+
+		match format!("{}\n\n{}", PUZZLE, solution).parse::<Grid>() {
+			Err(LoadError::ContradictingSolution(points)) => {
+				assert_eq!(points, vec![Vec2::new(1, 0)]);
+			}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	const LINE: &str =
+		".7.5.3.4.3.68.17..1..4.62...8....43...........57.....252.........8..4...9....8..5";
+	const LINE_SOLUTION: &str =
+		"872593146346821759195476283289165437613742598457389612521937864738654921964218375";
+
+	#[test]
+	fn parses_a_line_with_trailing_fields() {
+		let grid = format!("{},{},8.3", LINE, LINE_SOLUTION)
+			.parse::<Grid>()
+			.unwrap();
+
+		assert_eq!(grid.to_line(true), format!("{},{}", LINE, LINE_SOLUTION));
+	}
+
+	#[test]
+	fn reports_the_line_length() {
+		match LINE[..80].parse::<Grid>() {
+			Err(LoadError::WrongLength { length: 80 }) => {}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+
+		match format!("{} {}", LINE, &LINE_SOLUTION[..79]).parse::<Grid>() {
+			Err(LoadError::MalformedSolution(error)) => match *error {
+				LoadError::WrongLength { length: 79 } => {}
+				other => panic!("unexpected solution error {:?}", other),
+			},
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	#[test]
+	fn reports_line_characters_by_row_and_column() {
+		let line = format!("{}x{}", &LINE[..40], &LINE[41..]);
+
+		match line.parse::<Grid>() {
+			Err(LoadError::BadCharacter {
+				row: 4,
+				column: 4,
+				character: 'x',
+			}) => {}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+
+	#[test]
+	fn reports_line_solutions_that_contradict_givens() {
+		let solution = format!("827{}", &LINE_SOLUTION[3..]);
+
+		match format!("{};{}", LINE, solution).parse::<Grid>() {
+			Err(LoadError::ContradictingSolution(points)) => {
+				assert_eq!(points, vec![Vec2::new(1, 0)]);
+			}
+			other => panic!("unexpected result {:?}", other.err()),
+		}
+	}
+}
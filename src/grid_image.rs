@@ -1,12 +1,14 @@
 use ab_glyph::{FontRef, PxScale};
-use image::{Rgb, RgbImage};
+use image::{ImageError, Rgb, RgbImage};
 use imageproc::{
-	drawing::{draw_filled_rect_mut, draw_text_mut},
+	drawing::{draw_filled_rect_mut, draw_hollow_rect_mut, draw_line_segment_mut, draw_text_mut},
 	rect::Rect,
 };
 
 use crate::{
+	chain::{Chain, Link, Node},
 	grid::Grid,
+	step::Step,
 	vec2::{Vec2, SUDOKU},
 };
 
@@ -19,6 +21,13 @@ pub struct GridImage<'a> {
 	height: u32,
 }
 
+const COLORS: [Rgb<u8>; 4] = [
+	Rgb([20u8, 150u8, 60u8]),
+	Rgb([170u8, 40u8, 190u8]),
+	Rgb([0u8, 150u8, 170u8]),
+	Rgb([200u8, 120u8, 0u8]),
+];
+
 const OFFSETS: [(i32, i32); 9] = [
 	(4, 2),
 	(28, 2),
@@ -100,13 +109,13 @@ impl<'a> GridImage<'a> {
 			if y % 3 == 0 {
 				draw_filled_rect_mut(
 					&mut self.image,
-					Rect::at(0, y as i32 * self.cell_size).of_size(self.width, 2),
+					Rect::at(0, y * self.cell_size).of_size(self.width, 2),
 					Rgb([0u8, 0u8, 0u8]),
 				);
 			} else {
 				draw_filled_rect_mut(
 					&mut self.image,
-					Rect::at(0, y as i32 * self.cell_size).of_size(self.width, 1),
+					Rect::at(0, y * self.cell_size).of_size(self.width, 1),
 					Rgb([100u8, 100u8, 100u8]),
 				);
 			}
@@ -116,27 +125,27 @@ impl<'a> GridImage<'a> {
 			if x % 3 == 0 {
 				draw_filled_rect_mut(
 					&mut self.image,
-					Rect::at(x as i32 * self.cell_size, 0).of_size(2, self.height),
+					Rect::at(x * self.cell_size, 0).of_size(2, self.height),
 					Rgb([0u8, 0u8, 0u8]),
 				);
 			} else {
 				draw_filled_rect_mut(
 					&mut self.image,
-					Rect::at(x as i32 * self.cell_size, 0).of_size(1, self.height),
+					Rect::at(x * self.cell_size, 0).of_size(1, self.height),
 					Rgb([100u8, 100u8, 100u8]),
 				);
 			}
 		}
 	}
 
-	fn draw_candidate(&mut self, point: &Vec2, number: u8) {
+	fn draw_candidate(&mut self, point: &Vec2, number: u8, color: Rgb<u8>) {
 		let font_size = 20;
 
 		let (x_offset, y_offset) = OFFSETS[number as usize - 1];
 
 		draw_text_mut(
 			&mut self.image,
-			Rgb([30u8, 30u8, 30u8]),
+			color,
 			point.x as i32 * self.cell_size + x_offset,
 			point.y as i32 * self.cell_size + y_offset,
 			PxScale {
@@ -151,12 +160,85 @@ impl<'a> GridImage<'a> {
 	pub fn draw_candidates(&mut self) {
 		for point in SUDOKU.iter() {
 			for candidate in self.grid.get_candidates(point).iter() {
-				self.draw_candidate(point, *candidate);
+				self.draw_candidate(point, candidate, Rgb([30u8, 30u8, 30u8]));
 			}
 		}
 	}
 
-	pub fn save(&self) {
-		self.image.save("sudoku.png").expect("Could not save image");
+	fn draw_outline(&mut self, point: &Vec2, color: Rgb<u8>) {
+		draw_hollow_rect_mut(
+			&mut self.image,
+			Rect::at(
+				point.x as i32 * self.cell_size + 2,
+				point.y as i32 * self.cell_size + 2,
+			)
+			.of_size(self.cell_size as u32 - 3, self.cell_size as u32 - 3),
+			color,
+		);
+	}
+
+	fn candidate_center(&self, (point, number): &Node) -> (f32, f32) {
+		let (x_offset, y_offset) = OFFSETS[*number as usize - 1];
+		return (
+			(point.x as i32 * self.cell_size + x_offset + 6) as f32,
+			(point.y as i32 * self.cell_size + y_offset + 12) as f32,
+		);
+	}
+
+	pub fn draw_chain(&mut self, chain: &Chain) {
+		for (index, link) in chain.links.iter().enumerate() {
+			let color = match link {
+				Link::Strong => Rgb([30u8, 90u8, 220u8]),
+				Link::Weak => Rgb([230u8, 140u8, 20u8]),
+			};
+
+			let from = self.candidate_center(&chain.nodes[index]);
+			let to = self.candidate_center(&chain.nodes[index + 1]);
+			draw_line_segment_mut(&mut self.image, from, to, color);
+		}
+
+		for (point, number) in chain.nodes.iter() {
+			self.draw_candidate(point, *number, Rgb([30u8, 90u8, 220u8]));
+		}
+	}
+
+	pub fn draw_step(&mut self, step: &Step) {
+		for point in step.cells.iter() {
+			self.draw_outline(point, Rgb([30u8, 90u8, 220u8]));
+		}
+
+		for (index, set) in step.sets.iter().enumerate() {
+			for point in set.cells.iter() {
+				self.draw_outline(point, COLORS[index % COLORS.len()]);
+			}
+		}
+
+		for point in step.fins.iter() {
+			self.draw_outline(point, Rgb([230u8, 140u8, 20u8]));
+		}
+
+		for (index, group) in step.colors.iter().enumerate() {
+			for point in group.iter() {
+				for number in step.digits.iter() {
+					self.draw_candidate(point, number, COLORS[index % COLORS.len()]);
+				}
+			}
+		}
+
+		if let Some(chain) = &step.chain {
+			self.draw_chain(chain);
+		}
+
+		for (point, candidate) in step.eliminations.iter() {
+			self.draw_candidate(point, *candidate, Rgb([220u8, 30u8, 30u8]));
+		}
+	}
+
+	pub fn into_image(self) -> RgbImage {
+		self.image
+	}
+
+	pub fn save(&self, path: &str) -> Result<(), ImageError> {
+		return self.image.save(path);
 	}
 }
@@ -0,0 +1,38 @@
+//! Logic-first sudoku solver. Load a puzzle into a [`Grid`], run an [`Analysis`] over it and
+//! either read the grid back, inspect the steps the analysis took or draw it with [`GridImage`].
+
+mod almost_locked_set;
+mod analysis;
+mod batch;
+mod builtin;
+mod candidates;
+mod chain;
+mod combinations;
+mod grid;
+mod grid_image;
+mod line;
+mod load_error;
+mod mini_line;
+mod solutions;
+mod square;
+mod step;
+mod technique;
+mod unit;
+mod vec2;
+
+pub use crate::{
+	almost_locked_set::AlmostLockedSet,
+	analysis::{Analysis, Stepping},
+	batch::{Batch, BatchResult},
+	builtin::{builtin, builtins},
+	candidates::Candidates,
+	chain::{Chain, Link, Node},
+	grid::{CellValue, Coord, Grid},
+	grid_image::GridImage,
+	load_error::LoadError,
+	solutions::Solutions,
+	step::Step,
+	technique::Technique,
+	unit::Unit,
+	vec2::{Vec2, SUDOKU},
+};
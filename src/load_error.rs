@@ -0,0 +1,71 @@
+use std::fmt::Display;
+
+use crate::vec2::Vec2;
+
+#[derive(Debug)]
+pub enum LoadError {
+	Io(std::io::Error),
+	BadCharacter {
+		row: usize,
+		column: usize,
+		character: char,
+	},
+	WrongRowLength {
+		row: usize,
+		length: usize,
+	},
+	WrongRowCount {
+		rows: usize,
+	},
+	WrongLength {
+		length: usize,
+	},
+	MalformedSolution(Box<LoadError>),
+	ConflictingGivens(Vec<Vec2>),
+	ContradictingSolution(Vec<Vec2>),
+}
+
+impl Display for LoadError {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			LoadError::Io(error) => write!(f, "could not read puzzle: {}", error),
+			LoadError::BadCharacter {
+				row,
+				column,
+				character,
+			} => write!(
+				f,
+				"unexpected character '{}' at row {}, column {}",
+				character,
+				row + 1,
+				column + 1
+			),
+			LoadError::WrongRowLength { row, length } => {
+				write!(f, "row {} has {} cells, expected 9", row + 1, length)
+			}
+			LoadError::WrongRowCount { rows } => write!(f, "found {} rows, expected 9", rows),
+			LoadError::WrongLength { length } => {
+				write!(f, "found {} cells, expected 81", length)
+			}
+			LoadError::MalformedSolution(error) => write!(f, "malformed solution: {}", error),
+			LoadError::ConflictingGivens(points) => {
+				write!(f, "givens conflict at")?;
+				for point in points.iter() {
+					write!(f, " {}", point)?;
+				}
+
+				Ok(())
+			}
+			LoadError::ContradictingSolution(points) => {
+				write!(f, "solution contradicts the givens at")?;
+				for point in points.iter() {
+					write!(f, " {}", point)?;
+				}
+
+				Ok(())
+			}
+		}
+	}
+}
+
+impl std::error::Error for LoadError {}
@@ -1,36 +1,202 @@
-use crate::analysis::DEBUG;
+use sudoku_solver::{Analysis, Batch, Grid, Solutions, SUDOKU};
 
-mod analysis;
-mod grid;
-mod grid_image;
-mod line;
-mod mini_line;
-mod square;
-mod vec2;
+use crate::cli::{Cli, Mode};
+
+mod cli;
 
 fn main() {
-	let mut grid = grid::Grid::new();
-	grid.load();
-	grid.verify_data_structure();
+	let cli = match Cli::parse(std::env::args().skip(1)) {
+		Ok(cli) => cli,
+		Err(error) if error.len() == 0 => {
+			println!("{}", cli::usage());
+			return;
+		}
+		Err(error) => {
+			eprintln!("{}\n\n{}", error, cli::usage());
+			std::process::exit(2);
+		}
+	};
+
+	if cli.mode == Mode::Batch {
+		if !batch(&cli) {
+			std::process::exit(1);
+		}
+
+		return;
+	}
+
+	let mut success = true;
+	for (index, input) in cli.inputs.iter().enumerate() {
+		let mut grid = Grid::new();
+		if let Err(error) = grid.load(input) {
+			eprintln!("{}: {}", input, error);
+			success = false;
+			continue;
+		}
+
+		let output = cli.output_path(index);
+		success &= match cli.mode {
+			Mode::Solve => solve(&cli, &mut grid, &output),
+			Mode::Render => render(&mut grid, &output),
+			Mode::Check => check(&cli, input, &mut grid),
+			Mode::Rate => rate(&cli, input, &mut grid),
+			Mode::Batch => unreachable!(),
+		};
+	}
+
+	if !success {
+		std::process::exit(1);
+	}
+}
+
+fn solve(cli: &Cli, grid: &mut Grid, output: &str) -> bool {
+	let mut analysis = Analysis::new(grid, cli.cheating);
+	analysis.image_path = String::from(output);
+	analysis.stepping = cli.stepping;
+	analysis.techniques = cli.techniques.clone();
+	analysis.unique = cli.unique;
+
+	if analysis.stepping.pauses() {
+		analysis.pause();
+	}
+
+	analysis.run();
+
+	println!("{}", analysis.grid.to_line(false));
+
+	if let Err(error) = analysis.draw() {
+		eprintln!("{}: could not save image: {}", output, error);
+		return false;
+	}
+
+	return analysis.grid.is_solved();
+}
+
+fn render(grid: &mut Grid, output: &str) -> bool {
+	let mut analysis = Analysis::new(grid, false);
+	analysis.image_path = String::from(output);
 
-	let mut analysis = analysis::Analysis::new(&mut grid, false);
-	analysis.calculate_all_candidates();
+	if let Err(error) = analysis.draw() {
+		eprintln!("{}: could not save image: {}", output, error);
+		return false;
+	}
+
+	return true;
+}
+
+fn check(cli: &Cli, input: &str, grid: &mut Grid) -> bool {
+	let mut analysis = Analysis::new(grid, false);
+
+	let mut valid = analysis.verify();
+	for point in analysis.grid.invalid_cells.iter() {
+		println!("{}: conflicting given at {}", input, point);
+	}
+
+	if valid {
+		match analysis.count_solutions(cli.limit) {
+			Solutions::None => {
+				println!("{}: has no solution", input);
+				valid = false;
+			}
+			Solutions::Unique(solved) => {
+				println!("{}: has a unique solution", input);
+
+				let solution = &analysis.grid.solution;
+				if solution.len() != 0
+					&& SUDOKU
+						.iter()
+						.any(|point| solution.get(point) != Some(&solved.get_number(point)))
+				{
+					println!("{}: embedded solution is not the puzzle's solution", input);
+					valid = false;
+				}
+			}
+			Solutions::Multiple {
+				count,
+				first,
+				second,
+				differences,
+			} => {
+				let at_least = if count >= cli.limit { "at least " } else { "" };
+				println!("{}: has {}{} solutions", input, at_least, count);
+				println!("  {}", first.to_line(false));
+				println!("  {}", second.to_line(false));
+
+				let differences = differences
+					.iter()
+					.map(|point| point.to_string())
+					.collect::<Vec<_>>()
+					.join(" ");
+				println!("  differing at {}", differences);
+
+				valid = false;
+			}
+		}
+	}
 
-	if DEBUG {
-		analysis.draw();
+	println!("{}: {}", input, if valid { "ok" } else { "invalid" });
 
-		let mut string = String::new();
-		std::io::stdin().read_line(&mut string).unwrap();
+	return valid;
+}
+
+fn rate(cli: &Cli, input: &str, grid: &mut Grid) -> bool {
+	let mut analysis = Analysis::new(grid, cli.cheating);
+	analysis.techniques = cli.techniques.clone();
+	analysis.unique = cli.unique;
+
+	let rounds = analysis.run();
+
+	let filled = SUDOKU
+		.iter()
+		.filter(|point| analysis.grid.has_number(point))
+		.count();
+	if analysis.grid.is_solved() && analysis.techniques_used().contains_key("cheat") {
+		println!("{}: solved in {} rounds by guessing", input, rounds);
+	} else if analysis.grid.is_solved() {
+		println!("{}: solved in {} rounds", input, rounds);
+	} else {
+		println!(
+			"{}: stalled after {} rounds with {}/81 cells filled",
+			input, rounds, filled
+		);
+	}
+
+	return analysis.grid.is_solved();
+}
+
+fn batch(cli: &Cli) -> bool {
+	let mut success = true;
+
+	let mut batch = Batch::new(cli.cheating);
+	batch.techniques = cli.techniques.clone();
+	batch.unique = cli.unique;
+	for input in cli.inputs.iter() {
+		if let Err(error) = batch.load(input) {
+			eprintln!("{}: could not read collection: {}", input, error);
+			success = false;
+		}
 	}
 
-	while analysis.round() != 0 {
-		if DEBUG {
-			analysis.draw();
+	match cli.output.as_deref() {
+		Some(output) => {
+			let report = if output.ends_with(".json") {
+				batch.to_json()
+			} else {
+				batch.to_csv()
+			};
+
+			if let Err(error) = std::fs::write(output, report) {
+				eprintln!("{}: could not write report: {}", output, error);
+				success = false;
+			}
 
-			let mut string = String::new();
-			std::io::stdin().read_line(&mut string).unwrap();
+			print!("{}", batch.summary());
+		}
+		None => {
+			print!("{}", batch.to_csv());
+			eprint!("{}", batch.summary());
 		}
 	}
 
-	analysis.draw();
+	return success;
 }
@@ -0,0 +1,42 @@
+use crate::{
+	grid::Grid,
+	vec2::{Vec2, SUDOKU},
+};
+
+pub enum Solutions {
+	None,
+	Unique(Box<Grid>),
+	Multiple {
+		count: usize,
+		first: Box<Grid>,
+		second: Box<Grid>,
+		differences: Vec<Vec2>,
+	},
+}
+
+impl Solutions {
+	pub fn from_found(count: usize, mut found: Vec<Grid>) -> Self {
+		if count == 0 {
+			return Solutions::None;
+		}
+
+		if count == 1 {
+			return Solutions::Unique(Box::new(found.remove(0)));
+		}
+
+		let second = found.remove(1);
+		let first = found.remove(0);
+		let differences = SUDOKU
+			.iter()
+			.filter(|point| first.get_number(point) != second.get_number(point))
+			.copied()
+			.collect();
+
+		Solutions::Multiple {
+			count,
+			first: Box::new(first),
+			second: Box::new(second),
+			differences,
+		}
+	}
+}
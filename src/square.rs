@@ -1,43 +1,25 @@
-use std::collections::HashSet;
-
 use crate::{
-	grid::{CellValue, Coord},
+	candidates::Candidates,
+	grid::{CellValue, Coord, Grid},
+	unit::Unit,
 	vec2::Vec2,
 };
 
-pub type SquareIndex = u8;
-
-#[derive(Clone, Debug, Default)]
-pub struct Square {
-	pub cells: HashSet<CellValue>,
-	pub columns: [usize; 3],
-	pub coords: Vec<Vec2>,
-	pub rows: [usize; 3],
+#[derive(Clone, Copy)]
+pub struct Square<'a> {
+	grid: &'a Grid,
 	pub x: Coord,
 	pub y: Coord,
 }
 
-impl Square {
-	pub fn new(x: Coord, y: Coord) -> Self {
-		let mut coords = vec![];
-		for x in x * 3..x * 3 + 3 {
-			for y in y * 3..y * 3 + 3 {
-				coords.push(Vec2::new(x, y));
-			}
-		}
-
-		Square {
-			cells: HashSet::default(),
-			columns: Default::default(),
-			coords,
-			rows: Default::default(),
-			x,
-			y,
-		}
+impl<'a> Square<'a> {
+	pub fn new(grid: &'a Grid, x: Coord, y: Coord) -> Self {
+		Square { grid, x, y }
 	}
 
 	pub fn has_number(&self, number: CellValue) -> bool {
-		self.cells.contains(&number)
+		self.coords()
+			.any(|point| self.grid.get_number(&point) == number)
 	}
 
 	pub fn square_coord_to_index(x: Coord, y: Coord) -> usize {
@@ -48,7 +30,31 @@ impl Square {
 		Square::square_coord_to_index(point.x / 3, point.y / 3)
 	}
 
-	pub fn coords(&self) -> impl Iterator<Item = &Vec2> {
-		self.coords.iter()
+	// row by row, the same order as the square's unit
+	pub fn coords(&self) -> impl Iterator<Item = Vec2> + use<> {
+		self.unit().coords().into_iter()
+	}
+
+	pub fn candidate_coords(&self, number: CellValue) -> Vec<Vec2> {
+		self.coords()
+			.filter(|point| self.grid.get_candidates(point).contains(number))
+			.collect()
+	}
+
+	pub fn unit(&self) -> Unit {
+		Unit::Square(self.x + self.y * 3)
+	}
+
+	pub fn verify(&self) -> Option<Vec2> {
+		let mut set = Candidates::new();
+
+		for point in self.coords() {
+			let number = self.grid.get_number(&point);
+			if number != 0 && !set.insert(number) {
+				return Some(point);
+			}
+		}
+
+		return None;
 	}
 }
@@ -0,0 +1,81 @@
+use std::fmt::Display;
+
+use crate::{
+	almost_locked_set::AlmostLockedSet, candidates::Candidates, chain::Chain, grid::CellValue,
+	unit::Unit, vec2::Vec2,
+};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Step {
+	pub cells: Vec<Vec2>,
+	pub chain: Option<Chain>,
+	pub colors: Vec<Vec<Vec2>>,
+	pub digits: Candidates,
+	pub eliminations: Vec<(Vec2, CellValue)>,
+	pub fins: Vec<Vec2>,
+	pub placements: Vec<(Vec2, CellValue)>,
+	pub round: usize,
+	pub sets: Vec<AlmostLockedSet>,
+	pub technique: &'static str,
+	pub units: Vec<Unit>,
+}
+
+impl Display for Step {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{}", self.technique)?;
+
+		if !self.digits.is_empty() {
+			write!(f, " {}", self.digits)?;
+		}
+
+		for (index, unit) in self.units.iter().enumerate() {
+			write!(f, "{} {}", if index == 0 { " in" } else { "," }, unit)?;
+		}
+
+		if let Some(chain) = &self.chain {
+			write!(f, " {}", chain)?;
+		}
+
+		for (index, point) in self.cells.iter().enumerate() {
+			write!(f, "{} {}", if index == 0 { " at" } else { "" }, point)?;
+		}
+
+		for (index, group) in self.colors.iter().enumerate() {
+			write!(f, "{} {{", if index == 0 { ", colored" } else { "" })?;
+			for point in group.iter() {
+				write!(f, " {}", point)?;
+			}
+			write!(f, " }}")?;
+		}
+
+		for (index, set) in self.sets.iter().enumerate() {
+			write!(
+				f,
+				"{} [{}]",
+				if index == 0 { " with sets" } else { "" },
+				set
+			)?;
+		}
+
+		for (index, point) in self.fins.iter().enumerate() {
+			write!(
+				f,
+				"{} {}",
+				if index == 0 { " with fins at" } else { "" },
+				point
+			)?;
+		}
+
+		for (index, (point, candidate)) in self.eliminations.iter().enumerate() {
+			let separator = if index == 0 { ", removing" } else { "," };
+			write!(f, "{} {} from {}", separator, candidate, point)?;
+		}
+
+		for (index, (point, number)) in self.placements.iter().enumerate() {
+			let separator = if index == 0 { ", placing" } else { "," };
+			write!(f, "{} {} at {}", separator, number, point)?;
+		}
+
+		Ok(())
+	}
+}
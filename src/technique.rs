@@ -0,0 +1,22 @@
+use std::fmt::Debug;
+
+use crate::{analysis::Analysis, step::Step};
+
+pub trait Technique: Debug {
+	fn name(&self) -> &'static str;
+
+	fn find(&self, analysis: &Analysis) -> Vec<Step>;
+
+	fn assumes_unique(&self) -> bool {
+		false
+	}
+
+	fn last_resort(&self) -> bool {
+		false
+	}
+
+	// names steps can carry instead of the technique's own, such as each kind of chain
+	fn reports(&self) -> &[&'static str] {
+		&[]
+	}
+}
@@ -0,0 +1,50 @@
+use std::fmt::Display;
+
+use crate::{grid::Coord, vec2::Vec2};
+
+#[derive(Clone, Copy, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub enum Unit {
+	Row(Coord),
+	Column(Coord),
+	Square(Coord),
+}
+
+impl Unit {
+	pub fn all() -> impl Iterator<Item = Unit> {
+		(0..9)
+			.map(Unit::Row)
+			.chain((0..9).map(Unit::Column))
+			.chain((0..9).map(Unit::Square))
+	}
+
+	pub fn coords(&self) -> [Vec2; 9] {
+		match *self {
+			Unit::Row(y) => std::array::from_fn(|x| Vec2::new(x as Coord, y)),
+			Unit::Column(x) => std::array::from_fn(|y| Vec2::new(x, y as Coord)),
+			Unit::Square(index) => std::array::from_fn(|cell| {
+				Vec2::new(
+					(index % 3) * 3 + cell as Coord % 3,
+					(index / 3) * 3 + cell as Coord / 3,
+				)
+			}),
+		}
+	}
+
+	pub fn contains(&self, point: &Vec2) -> bool {
+		match *self {
+			Unit::Row(y) => point.y == y,
+			Unit::Column(x) => point.x == x,
+			Unit::Square(index) => (point.x / 3 + (point.y / 3) * 3) == index,
+		}
+	}
+}
+
+impl Display for Unit {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		match self {
+			Unit::Row(y) => write!(f, "row {}", y + 1),
+			Unit::Column(x) => write!(f, "column {}", x + 1),
+			Unit::Square(index) => write!(f, "square {}", index + 1),
+		}
+	}
+}
@@ -0,0 +1,100 @@
+use sudoku_solver::{builtin, Analysis, Grid, Solutions, Technique, Vec2};
+
+const SOLUTION: &str =
+	"872593146346821759195476283289165437613742598457389612521937864738654921964218375";
+
+fn count(line: &str, limit: usize) -> Solutions {
+	let mut grid = line.parse::<Grid>().unwrap();
+	return Analysis::new(&mut grid, false).count_solutions(limit);
+}
+
+#[test]
+fn finds_no_solution() {
+	// r1c9 can't hold anything once the 9 in its column is given
+	let line = format!("12345678.........9{}", ".".repeat(63));
+
+	assert!(matches!(count(&line, 5), Solutions::None));
+}
+
+#[test]
+fn finds_a_unique_solution() {
+	let line = ".7.5.3.4.3.68.17..1..4.62...8....43...........57.....252.........8..4...9....8..5";
+
+	match count(line, 5) {
+		Solutions::Unique(solved) => assert_eq!(solved.to_line(false), SOLUTION),
+		_ => panic!("expected a unique solution"),
+	}
+}
+
+#[test]
+fn finds_both_sides_of_a_deadly_pattern() {
+	// the solution with the 7s and 9s of r1c2, r1c5, r3c2 and r3c5 taken out
+	let line = "8.25.31463468217591.54.6283289165437613742598457389612521937864738654921964218375";
+
+	match count(line, 5) {
+		Solutions::Multiple {
+			count,
+			first,
+			second,
+			differences,
+		} => {
+			assert_eq!(count, 2);
+			assert_ne!(first.to_line(false), second.to_line(false));
+			assert!([first.to_line(false), second.to_line(false)].contains(&String::from(SOLUTION)));
+			assert_eq!(
+				differences,
+				vec![
+					Vec2::new(1, 0),
+					Vec2::new(1, 2),
+					Vec2::new(4, 0),
+					Vec2::new(4, 2)
+				]
+			);
+		}
+		_ => panic!("expected two solutions"),
+	}
+}
+
+#[test]
+fn stops_counting_at_the_limit() {
+	match count(&".".repeat(81), 5) {
+		Solutions::Multiple { count, .. } => assert_eq!(count, 5),
+		_ => panic!("expected several solutions"),
+	}
+
+	// the limit never drops below two, so a unique solution is still told apart
+	match count(&".".repeat(81), 1) {
+		Solutions::Multiple { count, .. } => assert_eq!(count, 2),
+		_ => panic!("expected several solutions"),
+	}
+}
+
+// singles keep the stalled rounds cheap
+fn singles() -> Vec<&'static dyn Technique> {
+	["single in squares", "single in rows", "single in columns"]
+		.iter()
+		.map(|name| builtin(name).unwrap())
+		.collect()
+}
+
+#[test]
+fn guesses_once_logic_stalls() {
+	let line = "1....7.9..3..2...8..96..5....53..9...1..8...26....4...3......1..4......7..7...3..";
+	let solution =
+		"162857493534129678789643521475312986913586742628794135356478219241935867897261354";
+
+	let mut grid = line.parse::<Grid>().unwrap();
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.techniques = singles();
+	assert_eq!(analysis.run(), 1);
+	assert!(!analysis.grid.is_solved());
+	assert!(!analysis.techniques_used().contains_key("cheat"));
+
+	// the round that guesses counts like any other
+	let mut grid = line.parse::<Grid>().unwrap();
+	let mut analysis = Analysis::new(&mut grid, true);
+	analysis.techniques = singles();
+	assert_eq!(analysis.run(), 2);
+	assert_eq!(analysis.grid.to_line(false), solution);
+	assert!(analysis.techniques_used().contains_key("cheat"));
+}
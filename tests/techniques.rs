@@ -0,0 +1,1312 @@
+use std::collections::BTreeSet;
+
+use sudoku_solver::{builtin, Analysis, CellValue, Coord, Grid, Step, Technique, Vec2};
+
+// 81 cells row by row, a lone digit is placed and anything else lists the candidates, with a
+// single candidate written as (5)
+fn state(cells: &str) -> Grid {
+	let tokens = cells.split_whitespace().collect::<Vec<_>>();
+	assert_eq!(tokens.len(), 81);
+
+	let mut grid = Grid::new();
+	for (index, token) in tokens.iter().enumerate() {
+		let point = Vec2::new((index % 9) as Coord, (index / 9) as Coord);
+		if token.len() == 1 {
+			grid.insert_number(&point, token.parse().unwrap());
+		}
+	}
+
+	for (index, token) in tokens.iter().enumerate() {
+		let point = Vec2::new((index % 9) as Coord, (index / 9) as Coord);
+		if token.len() != 1 {
+			let candidates = token
+				.trim_matches(['(', ')'])
+				.chars()
+				.map(|character| character as CellValue - b'0')
+				.collect();
+			grid.set_candidates(&point, candidates);
+		}
+	}
+
+	return grid;
+}
+
+fn find(analysis: &Analysis, technique: &str) -> Vec<Step> {
+	builtin(technique).unwrap().find(analysis)
+}
+
+fn describe(point: &Vec2, number: CellValue) -> String {
+	format!("{}r{}c{}", number, point.y + 1, point.x + 1)
+}
+
+// compares what the steps would still change on the grid, written like 5r1c2, and makes sure
+// none of it disagrees with the solution
+fn assert_changes(
+	analysis: &Analysis,
+	steps: &[Step],
+	solution: &str,
+	eliminations: &str,
+	placements: &str,
+) {
+	let solution = solution.as_bytes();
+	let solved = |point: &Vec2| solution[point.y as usize * 9 + point.x as usize] - b'0';
+
+	let mut eliminated = BTreeSet::new();
+	let mut placed = BTreeSet::new();
+	for step in steps.iter() {
+		for (point, number) in step.eliminations.iter() {
+			if analysis.grid.get_candidates(point).contains(*number) {
+				assert_ne!(solved(point), *number, "{} removes the solution", step);
+				eliminated.insert(describe(point, *number));
+			}
+		}
+
+		for (point, number) in step.placements.iter() {
+			if !analysis.grid.has_number(point) {
+				assert_eq!(solved(point), *number, "{} places the wrong number", step);
+				placed.insert(describe(point, *number));
+			}
+		}
+	}
+
+	let expected = |list: &str| list.split_whitespace().map(String::from).collect();
+	assert_eq!(eliminated, expected(eliminations));
+	assert_eq!(placed, expected(placements));
+}
+
+fn sized(steps: Vec<Step>, size: usize) -> Vec<Step> {
+	steps
+		.into_iter()
+		.filter(|step| step.digits.len() == size)
+		.collect()
+}
+
+fn named(steps: Vec<Step>, technique: &str) -> Vec<Step> {
+	steps
+		.into_iter()
+		.filter(|step| step.technique == technique)
+		.collect()
+}
+
+// forcing finds a lot at once, one step is enough to pin down
+fn first(steps: Vec<Step>) -> Vec<Step> {
+	steps.into_iter().take(1).collect()
+}
+
+#[test]
+fn naked_pair() {
+	let mut grid = state(
+		"4 2 1369 89 36 7 18 5 69
+		7 369 369 3689 5 1 4 38 2
+		56 15 8 69 4 2 137 37 69
+		9 14 14 7 8 6 5 2 3
+		25 7 25 1 9 3 6 4 8
+		8 36 36 4 2 5 9 1 7
+		3 45 45679 256 67 8 27 69 1
+		1 8 2567 256 367 9 237 367 4
+		26 69 2679 23 1 4 78 36789 5",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&sized(find(&analysis, "naked subsets"), 2),
+		"423867159769351482518942376941786523275193648836425917354678291182539764697214835",
+		"6r3c1",
+		"",
+	);
+}
+
+#[test]
+fn naked_triple() {
+	let mut grid = state(
+		"1479 5 14 2 3 467 167 8 67
+		12347 6 12348 148 178 9 157 12357 2357
+		12379 123789 1238 18 5 678 4 123679 2367
+		3467 3478 9 48 68 1 2 4567 4567
+		1246 124 5 7 269 246 3 146 8
+		12467 12478 1248 3 268 24568 9 14567 4567
+		1249 1249 7 5 1289 3 68 246 246
+		23459 2349 6 89 2789 278 578 23457 1
+		8 123 123 6 4 27 57 2357 9",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&sized(find(&analysis, "naked subsets"), 3),
+		"951234687264879153783156492379481265645792318128365974497513826536928741812647539",
+		"8r8c4",
+		"",
+	);
+}
+
+#[test]
+fn naked_quad() {
+	let mut grid = state(
+		"4 8 6 5 2 9 1 7 3
+		7 5 9 14 3 14 8 26 26
+		12 12 3 7 6 8 4 5 9
+		6 9 12 8 4 3 5 12 7
+		125 4 7 6 9 25 3 8 12
+		2358 23 58 12 7 125 6 9 4
+		19 67 12 49 5 46 27 3 8
+		2358 36 58 23 1 7 9 4 56
+		359 37 4 39 8 26 27 16 15",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&sized(find(&analysis, "naked subsets"), 4),
+		"486529173759431862213768459691843527547692381328175694162954738835217946974386215",
+		"2r3c2",
+		"",
+	);
+}
+
+#[test]
+fn hidden_pair() {
+	let mut grid = state(
+		"4 8 6 5 2 9 1 7 3
+		7 5 9 14 3 14 8 26 26
+		12 12 3 7 6 8 4 5 9
+		6 9 12 8 4 3 5 12 7
+		125 4 7 6 9 25 3 8 12
+		2358 23 58 12 7 125 6 9 4
+		19 67 12 49 5 46 27 3 8
+		2358 36 58 23 1 7 9 4 56
+		359 37 4 39 8 26 27 16 15",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&sized(find(&analysis, "hidden subsets"), 2),
+		"486529173759431862213768459691843527547692381328175694162954738835217946974386215",
+		"3r6c2",
+		"",
+	);
+}
+
+#[test]
+fn hidden_triple() {
+	let mut grid = state(
+		"4 2 1369 89 36 7 18 5 69
+		7 369 369 3689 5 1 4 38 2
+		56 15 8 69 4 2 137 37 69
+		9 14 14 7 8 6 5 2 3
+		25 7 25 1 9 3 6 4 8
+		8 36 36 4 2 5 9 1 7
+		3 45 45679 256 67 8 27 69 1
+		1 8 2567 256 367 9 237 367 4
+		26 69 2679 23 1 4 78 36789 5",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&sized(find(&analysis, "hidden subsets"), 3),
+		"423867159769351482518942376941786523275193648836425917354678291182539764697214835",
+		"5r3c2",
+		"",
+	);
+}
+
+#[test]
+fn hidden_quad() {
+	let mut grid = state(
+		"4 2 1369 89 36 7 18 5 69
+		7 369 369 3689 5 1 4 38 2
+		56 15 8 69 4 2 137 37 69
+		9 14 14 7 8 6 5 2 3
+		25 7 25 1 9 3 6 4 8
+		8 36 36 4 2 5 9 1 7
+		3 45 45679 256 67 8 27 69 1
+		1 8 2567 256 367 9 237 367 4
+		26 69 2679 23 1 4 78 36789 5",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&sized(find(&analysis, "hidden subsets"), 4),
+		"423867159769351482518942376941786523275193648836425917354678291182539764697214835",
+		"6r3c1",
+		"",
+	);
+}
+
+#[test]
+fn hidden_single_in_a_row() {
+	let mut grid = state(
+		"5 46 24678 9 247 2348 378 1 2378
+		24789 1 3 247 247 248 5789 6 2578
+		26789 69 26789 12367 127 5 3789 2389 4
+		1346789 2 46789 147 14579 149 1345689 34589 13568
+		13489 349 5 124 6 1249 13489 7 138
+		14679 469 4679 8 3 149 2 459 156
+		2349 7 1 234 8 6 345 2345 235
+		23469 34569 2469 1234 1249 12349 1345678 23458 1235678
+		2346 8 246 5 124 7 1346 234 9",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "single in rows"),
+		"546923817213478965897615324428751693135269478769834251971386542354192786682547139",
+		"",
+		"9r7c1",
+	);
+}
+
+#[test]
+fn hidden_single_in_a_column() {
+	let mut grid = state(
+		"1679 3 1679 2 5 178 79 4 1678
+		1245679 45679 8 36 1367 17 2379 12679 12367
+		1267 67 1267 368 4 9 237 12678 5
+		1245789 45789 3 48 128 6 2479 25789 2478
+		245689 45689 24569 7 238 248 1 25689 23468
+		124678 4678 12467 5 9 1248 2347 2678 234678
+		34567 4567 4567 1 267 2457 8 27 9
+		45789 2 4579 489 78 4578 6 3 147
+		46789 1 4679 4689 2678 3 5 27 247",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "single in columns"),
+		"639251748458367912172849365583416297294738156761592483346125879825974631917683524",
+		"",
+		"3r7c1",
+	);
+}
+
+#[test]
+fn x_wing() {
+	let mut grid = state(
+		"123468 7 12468 12369 13689 12389 234689 5 24689
+		123568 13568 1268 12369 4 123589 7 12368 2689
+		1234568 13568 9 12367 13568 123578 23468 12368 2468
+		134689 2 7 13469 13569 1359 5689 68 689
+		1469 169 5 8 7 129 269 26 3
+		3689 3689 68 2369 3569 2359 1 4 26789
+		15678 1568 168 137 2 4 368 9 68
+		12789 4 128 1379 1389 6 238 238 5
+		2689 689 3 5 89 89 2468 7 1",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "x-wing"),
+		"174683952632945718589217634327461589415879263896352147758124396941736825263598471",
+		"7r3c4",
+		"",
+	);
+}
+
+#[test]
+fn swordfish() {
+	let mut grid = state(
+		"12478 124678 1346 349 5 3469 123469 1349 13467
+		457 9 3456 2 1 346 8 34 3467
+		124 1246 1346 7 8 3469 123469 5 1346
+		3 1478 14 19 279 12789 5 6 148
+		9 14678 146 5 37 1378 134 2 1348
+		158 158 2 6 4 138 7 138 9
+		1245 3 1459 149 269 124569 1469 7 14568
+		6 145 8 1349 379 134579 1349 1349 2
+		1245 1245 7 8 2369 1234569 13469 1349 13456",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "swordfish"),
+		"861354297795216843423789156374928561986571324512643789239165478658437912147892635",
+		"7r1c2",
+		"",
+	);
+}
+
+#[test]
+fn jellyfish() {
+	let mut grid = state(
+		"4 8 6 5 2 9 1 7 3
+		7 5 9 14 3 14 8 26 26
+		12 12 3 7 6 8 4 5 9
+		6 9 12 8 4 3 5 12 7
+		125 4 7 6 9 25 3 8 12
+		2358 23 58 12 7 125 6 9 4
+		19 67 12 49 5 46 27 3 8
+		2358 36 58 23 1 7 9 4 56
+		359 37 4 39 8 26 27 16 15",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "jellyfish"),
+		"486529173759431862213768459691843527547692381328175694162954738835217946974386215",
+		"1r3c1",
+		"",
+	);
+}
+
+#[test]
+fn finned_x_wing() {
+	let mut grid = state(
+		"125 12589 14589 123678 12678 123678 1345689 45679 34578
+		3 7 6 18 18 9 1458 2 458
+		12 1289 189 5 4 123678 13689 679 378
+		4 59 2 67 3 567 56 8 1
+		6 13589 135789 1278 125789 12578 2345 45 2345
+		15 1358 1358 1268 12568 4 7 56 9
+		1257 12356 1357 9 125678 1235678 2458 457 24578
+		9 4 157 12678 125678 125678 258 3 2578
+		8 235 357 2347 257 2357 2459 1 6",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "finned x-wing"),
+		"254367198376189425189542673492736581617895342538214769763921854941658237825473916",
+		"4r9c7",
+		"",
+	);
+}
+
+#[test]
+fn sashimi_x_wing() {
+	let mut grid = state(
+		"4 2 1369 89 36 7 18 5 69
+		7 369 369 3689 5 1 4 38 2
+		56 15 8 69 4 2 137 37 69
+		9 14 14 7 8 6 5 2 3
+		25 7 25 1 9 3 6 4 8
+		8 36 36 4 2 5 9 1 7
+		3 45 45679 256 67 8 27 69 1
+		1 8 2567 256 367 9 237 367 4
+		26 69 2679 23 1 4 78 36789 5",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "sashimi x-wing"),
+		"423867159769351482518942376941786523275193648836425917354678291182539764697214835",
+		"3r2c8",
+		"",
+	);
+}
+
+#[test]
+fn finned_swordfish() {
+	let mut grid = state(
+		"1679 3 1679 2 5 178 79 4 1678
+		1245679 45679 8 36 1367 17 2379 12679 12367
+		1267 67 1267 368 4 9 237 12678 5
+		1245789 45789 3 48 128 6 2479 25789 2478
+		245689 45689 24569 7 238 248 1 25689 23468
+		124678 4678 12467 5 9 1248 2347 2678 234678
+		34567 4567 4567 1 267 2457 8 27 9
+		45789 2 4579 489 78 4578 6 3 147
+		46789 1 4679 4689 2678 3 5 27 247",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "finned swordfish"),
+		"639251748458367912172849365583416297294738156761592483346125879825974631917683524",
+		"3r5c9",
+		"",
+	);
+}
+
+#[test]
+fn sashimi_swordfish() {
+	let mut grid = state(
+		"1679 3 1679 2 5 178 79 4 1678
+		1245679 45679 8 36 1367 17 2379 12679 12367
+		1267 67 1267 368 4 9 237 12678 5
+		1245789 45789 3 48 128 6 2479 25789 2478
+		245689 45689 24569 7 238 248 1 25689 23468
+		124678 4678 12467 5 9 1248 2347 2678 234678
+		34567 4567 4567 1 267 2457 8 27 9
+		45789 2 4579 489 78 4578 6 3 147
+		46789 1 4679 4689 2678 3 5 27 247",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "sashimi swordfish"),
+		"639251748458367912172849365583416297294738156761592483346125879825974631917683524",
+		"3r2c5",
+		"",
+	);
+}
+
+#[test]
+fn finned_jellyfish() {
+	let mut grid = state(
+		"2 349 39 7 1346 3469 5 1489 14689
+		3459 34579 379 1346 8 3469 4679 1479 2
+		6 8 1 24 24 5 479 3 49
+		7 6 239 8 234 234 2349 1249 5
+		35 235 238 9 23467 1 23468 248 3468
+		139 1239 4 236 5 236 23689 1289 7
+		349 23479 6 2345 2347 23478 1 245789 3489
+		8 12347 5 1234 9 2347 2347 6 34
+		1349 123479 2379 123456 123467 234678 234789 245789 3489",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "finned jellyfish"),
+		"243769581957183642681245739769824315538971426124356897496532178815497263372618954",
+		"7r2c8",
+		"",
+	);
+}
+
+#[test]
+fn sashimi_jellyfish() {
+	let mut grid = state(
+		"4 2 1369 89 36 7 18 5 69
+		7 369 369 3689 5 1 4 38 2
+		56 15 8 69 4 2 137 37 69
+		9 14 14 7 8 6 5 2 3
+		25 7 25 1 9 3 6 4 8
+		8 36 36 4 2 5 9 1 7
+		3 45 45679 256 67 8 27 69 1
+		1 8 2567 256 367 9 237 367 4
+		26 69 2679 23 1 4 78 36789 5",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "sashimi jellyfish"),
+		"423867159769351482518942376941786523275193648836425917354678291182539764697214835",
+		"3r2c8",
+		"",
+	);
+}
+
+#[test]
+fn xy_wing() {
+	let mut grid = state(
+		"4 56 356 7 35 1 8 9 2
+		235 258 2358 4 359 59 7 1 6
+		7 1 9 2 6 8 3 4 5
+		2356 25678 1 69 28 2357 4 58 789
+		256 25678 4 69 1258 27 59 3 178
+		9 578 358 15 4 357 2 6 178
+		1 3 56 8 7 69 59 2 4
+		256 4 7 3 259 269 1 58 89
+		8 9 25 15 125 4 6 7 3",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "xy-wing"),
+		"456731892328459716719268345571623489264987531983145267135876924647392158892514673",
+		"5r2c3",
+		"",
+	);
+}
+
+#[test]
+fn xyz_wing() {
+	let mut grid = state(
+		"125 12589 14589 123678 12678 123678 1345689 45679 34578
+		3 7 6 18 18 9 1458 2 458
+		12 1289 189 5 4 123678 13689 679 378
+		4 59 2 67 3 567 56 8 1
+		6 13589 135789 1278 125789 12578 2345 45 2345
+		15 1358 1358 1268 12568 4 7 56 9
+		1257 12356 1357 9 125678 1235678 2458 457 24578
+		9 4 157 12678 125678 125678 258 3 2578
+		8 235 357 2347 257 2357 2459 1 6",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "xyz-wing"),
+		"254367198376189425189542673492736581617895342538214769763921854941658237825473916",
+		"1r7c1",
+		"",
+	);
+}
+
+#[test]
+fn w_wing() {
+	let mut grid = state(
+		"17 4 569 5689 579 3 189 2 789
+		8 269 269 2469 2479 1 3 5 79
+		17 259 3 289 2579 2589 189 4 6
+		4569 5689 7 3 4569 45 2 1 589
+		2 1 59 59 8 7 6 3 4
+		4569 3 45689 1 2569 245 7 89 589
+		45 258 1 2458 2459 6 89 7 3
+		3 89 48 7 1 489 5 6 2
+		569 7 2689 258 3 2589 4 89 1",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "w-wing"),
+		"149653827862471359753298146497365218215987634638124795521846973384719562976532481",
+		"5r4c1",
+		"",
+	);
+}
+
+#[test]
+fn skyscraper() {
+	let mut grid = state(
+		"6 135 7 1238 9 238 12358 1235 4
+		2348 13 248 5 23678 23678 12378 123 9
+		23589 1359 2589 4 2378 2378 123578 1235 6
+		34589 2 45689 38 34568 345689 1568 7 18
+		345789 35679 1 2378 2345678 23456789 2568 259 28
+		5789 5679 5689 278 1 256789 4 259 3
+		279 8 3 27 247 1 27 6 5
+		1 57 25 6 234578 234578 9 234 27
+		257 4 256 9 2357 2357 1237 8 127",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "skyscraper"),
+		"637198524214576839598432716425369178371854692869217453983741265152683947746925381",
+		"6r4c3",
+		"",
+	);
+}
+
+#[test]
+fn two_string_kite() {
+	let mut grid = state(
+		"1358 34589 34589 12457 145789 1249 1234678 134679 123689
+		2 34589 6 1457 145789 149 13478 13479 1389
+		18 7 489 3 1489 6 1248 5 1289
+		35678 35689 35789 12457 134579 12349 123468 1346 123568
+		358 1 2 45 6 349 348 34 7
+		4 356 357 1257 1357 8 9 136 12356
+		9 36 37 8 13 5 1367 2 4
+		3678 3468 1 9 2 34 5 367 36
+		356 23456 345 146 134 7 136 8 1369",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "two-string kite"),
+		"853492716246751398179386452798143265312569847465278931937815624681924573524637189",
+		"2r1c9",
+		"",
+	);
+}
+
+#[test]
+fn empty_rectangle() {
+	let mut grid = state(
+		"2 349 39 7 1346 3469 5 1489 14689
+		3459 34579 379 1346 8 3469 4679 1479 2
+		6 8 1 24 24 5 479 3 49
+		7 6 239 8 234 234 2349 1249 5
+		35 235 238 9 23467 1 23468 248 3468
+		139 1239 4 236 5 236 23689 1289 7
+		349 23479 6 2345 2347 23478 1 245789 3489
+		8 12347 5 1234 9 2347 2347 6 34
+		1349 123479 2379 123456 123467 234678 234789 245789 3489",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "empty rectangle"),
+		"243769581957183642681245739769824315538971426124356897496532178815497263372618954",
+		"7r9c7",
+		"",
+	);
+}
+
+#[test]
+fn color_wrap() {
+	let mut grid = state(
+		"15679 4 569 5689 5679 35789 1389 2 1789
+		8 269 269 2469 24679 1 39 5 79
+		1579 259 3 24589 24579 245789 189 4789 6
+		4569 5689 7 3 24569 2459 2689 1 589
+		2 1 569 569 8 579 369 39 4
+		4569 35689 45689 124569 124569 2459 7 389 589
+		459 2589 1 24589 2459 6 89 789 3
+		3 89 489 7 149 489 5 6 2
+		569 7 25689 12589 3 2589 4 89 189",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "color wrap"),
+		"149653827862471359753298146497365218215987634638124795521846973384719562976532481",
+		"3r5c7 3r6c8",
+		"",
+	);
+}
+
+#[test]
+fn color_trap() {
+	let mut grid = state(
+		"4 8 6 5 2 9 1 7 3
+		7 5 9 14 3 14 8 26 26
+		12 12 3 7 6 8 4 5 9
+		6 9 12 8 4 3 5 12 7
+		125 4 7 6 9 25 3 8 12
+		2358 23 58 12 7 125 6 9 4
+		19 67 12 49 5 46 27 3 8
+		2358 36 58 23 1 7 9 4 56
+		359 37 4 39 8 26 27 16 15",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "color trap"),
+		"486529173759431862213768459691843527547692381328175694162954738835217946974386215",
+		"1r3c1",
+		"",
+	);
+}
+
+#[test]
+fn multi_coloring() {
+	let mut grid = state(
+		"6 135 7 1238 9 238 12358 1235 4
+		2348 13 248 5 23678 23678 12378 123 9
+		23589 1359 2589 4 2378 2378 123578 1235 6
+		34589 2 45689 38 34568 345689 1568 7 18
+		345789 35679 1 2378 2345678 23456789 2568 259 28
+		5789 5679 5689 278 1 256789 4 259 3
+		279 8 3 27 247 1 27 6 5
+		1 57 25 6 234578 234578 9 234 27
+		257 4 256 9 2357 2357 1237 8 127",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "multi-coloring"),
+		"637198524214576839598432716425369178371854692869217453983741265152683947746925381",
+		"6r4c3",
+		"",
+	);
+}
+
+#[test]
+fn x_chain() {
+	let mut grid = state(
+		"17 4 569 5689 579 3 189 2 789
+		8 269 269 2469 2479 1 3 5 79
+		17 259 3 289 2579 2589 189 4 6
+		4569 5689 7 3 4569 45 2 1 589
+		2 1 59 59 8 7 6 3 4
+		4569 3 45689 1 2569 245 7 89 589
+		45 258 1 2458 2459 6 89 7 3
+		3 89 48 7 1 489 5 6 2
+		569 7 2689 258 3 2589 4 89 1",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&named(find(&analysis, "chains"), "x-chain"),
+		"149653827862471359753298146497365218215987634638124795521846973384719562976532481",
+		"9r6c5",
+		"",
+	);
+}
+
+#[test]
+fn xy_chain() {
+	let mut grid = state(
+		"2569 58 2689 4 2679 26789 57 3 1
+		569 7 4689 3 1 689 45 89 2
+		1 3 2489 78 2579 25789 6 89 47
+		5679 458 1 678 3 259 47 25 467
+		367 48 368 1 257 2578 9 25 467
+		5679 2 69 67 59 4 8 1 3
+		23 9 23 5 67 67 1 4 8
+		8 6 5 2 4 1 3 7 9
+		4 1 7 9 8 3 2 6 5",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&named(find(&analysis, "chains"), "xy-chain"),
+		"658492731974316582132875694581739426346128957729654813293567148865241379417983265",
+		"9r3c3",
+		"",
+	);
+}
+
+#[test]
+fn alternating_inference_chain() {
+	let mut grid = state(
+		"2 349 39 7 1346 3469 5 1489 14689
+		3459 34579 379 1346 8 3469 4679 1479 2
+		6 8 1 24 24 5 479 3 49
+		7 6 239 8 234 234 2349 1249 5
+		35 235 238 9 23467 1 23468 248 3468
+		139 1239 4 236 5 236 23689 1289 7
+		349 23479 6 2345 2347 23478 1 245789 3489
+		8 12347 5 1234 9 2347 2347 6 34
+		1349 123479 2379 123456 123467 234678 234789 245789 3489",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&named(find(&analysis, "chains"), "aic"),
+		"243769581957183642681245739769824315538971426124356897496532178815497263372618954",
+		"6r6c7",
+		"",
+	);
+}
+
+#[test]
+fn unique_rectangle_type_1() {
+	let mut grid = state(
+		"35 24 24 6 38 1 79 589 57
+		37 8 9 5 37 2 4 6 1
+		57 6 1 48 9 47 2 58 3
+		1 39 8 49 2 349 5 7 6
+		4 39 6 7 5 8 139 139 2
+		2 5 7 1 6 39 389 349 48
+		8 24 245 3 17 6 17 45 9
+		9 7 34 2 18 5 6 134 48
+		6 1 35 89 4 79 378 2 57",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.unique = true;
+
+	assert_changes(
+		&analysis,
+		&named(
+			find(&analysis, "unique rectangles"),
+			"unique rectangle type 1",
+		),
+		"342681957789532461561497283138924576496758312257163894825376149974215638613849725",
+		"2r7c3 4r7c3",
+		"",
+	);
+}
+
+#[test]
+fn unique_rectangle_type_2() {
+	let mut grid = state(
+		"6 78 9 138 2 148 45 178 35
+		134 78 134 9 13458 1458 6 178 2
+		1234 5 1234 7 13468 1468 49 18 39
+		57 2 56 168 1678 3 89 4 1689
+		9 3 8 2 146 146 7 5 16
+		47 1 46 5 9 678 2 3 68
+		8 9 125 16 57 257 3 26 4
+		12 4 7 136 136 9 58 26 58
+		235 6 235 4 58 258 1 9 7",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.unique = true;
+
+	assert_changes(
+		&analysis,
+		&named(
+			find(&analysis, "unique rectangles"),
+			"unique rectangle type 2",
+		),
+		"689124573374958612152736489725863941938241756416597238891675324247319865563482197",
+		"1r3c8",
+		"",
+	);
+}
+
+#[test]
+fn unique_rectangle_type_3() {
+	let mut grid = state(
+		"6 78 9 138 2 148 45 178 35
+		134 78 134 9 13458 1458 6 178 2
+		1234 5 1234 7 13468 1468 49 18 39
+		57 2 56 168 1678 3 89 4 1689
+		9 3 8 2 146 146 7 5 16
+		47 1 46 5 9 678 2 3 68
+		8 9 125 16 57 257 3 26 4
+		12 4 7 136 136 9 58 26 58
+		235 6 235 4 58 258 1 9 7",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.unique = true;
+
+	assert_changes(
+		&analysis,
+		&named(
+			find(&analysis, "unique rectangles"),
+			"unique rectangle type 3",
+		),
+		"689124573374958612152736489725863941938241756416597238891675324247319865563482197",
+		"1r3c8",
+		"",
+	);
+}
+
+#[test]
+fn unique_rectangle_type_4() {
+	let mut grid = state(
+		"4 8 6 5 2 9 1 7 3
+		7 5 9 14 3 14 8 26 26
+		12 12 3 7 6 8 4 5 9
+		6 9 12 8 4 3 5 12 7
+		125 4 7 6 9 25 3 8 12
+		2358 23 58 12 7 125 6 9 4
+		19 67 12 49 5 46 27 3 8
+		2358 36 58 23 1 7 9 4 56
+		359 37 4 39 8 26 27 16 15",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.unique = true;
+
+	assert_changes(
+		&analysis,
+		&named(
+			find(&analysis, "unique rectangles"),
+			"unique rectangle type 4",
+		),
+		"486529173759431862213768459691843527547692381328175694162954738835217946974386215",
+		"5r6c1 5r8c1",
+		"",
+	);
+}
+
+#[test]
+fn unique_rectangle_type_5() {
+	let mut grid = state(
+		"358 378 2356 35 9 138 1367 4 136
+		35 4 356 358 2 138 1367 3567 9
+		1 9 35 4 357 6 2 8 (3)
+		2 38 369 1 36 5 346 36 7
+		36 1 36 7 4 2 5 9 8
+		4 5 7 36 8 39 36 1 2
+		35 2 1 9 356 7 8 36 346
+		3579 37 8 356 356 4 13679 2367 136
+		379 6 4 2 1 38 379 37 5",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.unique = true;
+
+	assert_changes(
+		&analysis,
+		&named(
+			find(&analysis, "unique rectangles"),
+			"unique rectangle type 5",
+		),
+		"872593146346821759195476283289165437613742598457389612521937864738654921964218375",
+		"8r1c6",
+		"",
+	);
+}
+
+#[test]
+fn unique_rectangle_type_6() {
+	let mut grid = state(
+		"7 8 39 14 6 149 139 2 5
+		26 1 5 7 3 289 69 89 4
+		236 4 2369 5 18 1289 1369 189 7
+		5 27 1268 9 148 68 147 14 3
+		4 9 13 13 7 5 2 6 8
+		168 37 168 2 148 1368 147 5 9
+		9 5 7 146 2 14 8 3 16
+		128 23 4 168 9 13 5 7 126
+		1238 6 1238 138 5 7 49 49 12",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.unique = true;
+
+	assert_changes(
+		&analysis,
+		&named(
+			find(&analysis, "unique rectangles"),
+			"unique rectangle type 6",
+		),
+		"783469125215738694649512387572986413491375268836241759957624831124893576368157942",
+		"4r1c6 4r7c4",
+		"",
+	);
+}
+
+#[test]
+fn bug_plus_one() {
+	let mut grid = state(
+		"3 46 9 7 2 46 1 8 5
+		5 48 1 9 38 34 7 6 2
+		26 28 7 68 1 5 3 4 9
+		12 7 23 5 4 8 6 9 13
+		16 5 36 2 9 7 8 13 4
+		8 9 4 3 6 1 5 2 7
+		4 3 8 1 5 9 2 7 6
+		7 26 5 4 38 26 9 13 138
+		9 1 26 68 7 23 4 5 38",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.unique = true;
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "bug+1"),
+		"349726185581934762627815349273548691156297834894361527438159276765482913912673458",
+		"1r8c9 8r8c9",
+		"",
+	);
+}
+
+#[test]
+fn als_xz() {
+	let mut grid = state(
+		"17 4 569 5689 579 3 189 2 789
+		8 269 269 2469 2479 1 3 5 79
+		17 259 3 289 2579 2589 189 4 6
+		4569 5689 7 3 4569 459 2 1 589
+		2 1 59 59 8 7 6 3 4
+		4569 3 45689 1 2569 2459 7 89 589
+		459 2589 1 24589 2459 6 89 7 3
+		3 89 489 7 1 489 5 6 2
+		569 7 2689 2589 3 2589 4 89 1",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&analysis.als_xz(),
+		"149653827862471359753298146497365218215987634638124795521846973384719562976532481",
+		"9r8c3",
+		"",
+	);
+}
+
+#[test]
+fn als_xy_wing() {
+	let mut grid = state(
+		"17 4 569 5689 579 3 189 2 789
+		8 269 269 2469 2479 1 3 5 79
+		17 259 3 289 2579 2589 189 4 6
+		4569 5689 7 3 4569 459 2 1 589
+		2 1 59 59 8 7 6 3 4
+		4569 3 45689 1 2569 2459 7 89 589
+		459 2589 1 24589 2459 6 89 7 3
+		3 89 489 7 1 489 5 6 2
+		569 7 2689 2589 3 2589 4 89 1",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&analysis.als_xy_wings(),
+		"149653827862471359753298146497365218215987634638124795521846973384719562976532481",
+		"9r8c3",
+		"",
+	);
+}
+
+#[test]
+fn death_blossom() {
+	let mut grid = state(
+		"1256 1246 12 9 3 25 7 8 145
+		3589 489 3789 4578 1 6 359 3459 2
+		1235689 124689 123789 24578 24578 2578 13569 13459 145
+		128 5 128 6 278 4 138 1237 9
+		7 3 1289 1258 2589 2589 158 1245 6
+		12689 12689 4 123578 25789 235789 1358 12357 1578
+		189 189 189 4578 456789 5789 2 1579 3
+		4 7 6 2358 2589 1 589 59 58
+		12389 1289 5 2378 2789 23789 4 6 178",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&analysis.death_blossoms(),
+		"561932784847516392293847615152674839739258146684193527918465273476321958325789461",
+		"5r8c9",
+		"",
+	);
+}
+
+#[test]
+fn sue_de_coq() {
+	let mut grid = state(
+		"9 4 7 18 5 3 128 6 128
+		5 8 1 7 26 26 9 4 3
+		26 3 26 189 4 189 7 5 18
+		4 5 289 1239 13 7 6 1289 289
+		1 7 289 6 29 4 3 289 5
+		3 26 269 5 8 19 4 129 7
+		7 26 4 138 13 268 5 289 1289
+		268 1 3 289 269 5 28 7 4
+		28 9 5 4 7 128 12 3 6",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "sue de coq"),
+		"947153862581726943632849751459237618178694325326581497764312589813965274295478136",
+		"8r8c4",
+		"",
+	);
+}
+
+#[test]
+fn cell_forcing_chain() {
+	let mut grid = state(
+		"7 125689 124689 123689 12589 35689 1234 12369 2346
+		1246 3 124689 12689 7 689 124 1269 5
+		1256 12569 1269 4 1259 3569 8 123679 2367
+		23456 2569 23469 7 489 3489 235 23568 1
+		8 126 12346 5 14 34 9 2367 2367
+		135 7 139 1389 6 2 35 4 38
+		236 268 23678 268 2458 1 23457 23578 9
+		12 4 1278 289 3 5789 6 12578 278
+		9 1268 5 268 248 4678 12347 12378 23478",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&first(find(&analysis, "cell forcing chain")),
+		"784359162632178495591426837456793281823514976179862543368241759247935618915687324",
+		"3r6c1",
+		"",
+	);
+}
+
+#[test]
+fn unit_forcing_chain() {
+	let mut grid = state(
+		"2567 35 236 124678 2467 12468 12789 178 12789
+		9 1 26 3 267 268 4 5 278
+		27 4 8 127 5 9 3 17 6
+		4568 9 7 4568 1 4568 68 2 3
+		4568 35 1346 245678 2467 24568 1689 1468 1489
+		468 2 146 9 3 468 1678 14678 5
+		1 68 5 246 9 7 268 3 248
+		24 68 9 12456 246 3 125678 14678 12478
+		3 7 24 1456 8 12456 1256 9 124",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&first(find(&analysis, "unit forcing chain")),
+		"753641982916328457248759316697514823534872169821936745165497238489263571372185694",
+		"2r1c3 3r1c2 3r5c3 5r1c1 5r5c2 6r1c3",
+		"",
+	);
+}
+
+#[test]
+fn cell_forcing_net() {
+	let mut grid = state(
+		"3 46 9 7 2 46 1 8 5
+		5 48 1 9 38 34 7 6 2
+		26 28 7 68 1 5 3 4 9
+		12 7 23 5 4 8 6 9 13
+		16 5 36 2 9 7 8 13 4
+		8 9 4 3 6 1 5 2 7
+		4 3 8 1 5 9 2 7 6
+		7 26 5 4 38 26 9 13 138
+		9 1 26 68 7 23 4 5 38",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&first(find(&analysis, "cell forcing net")),
+		"349726185581934762627815349273548691156297834894361527438159276765482913912673458",
+		"1r4c1 1r5c8 1r8c9 2r3c1 2r4c3 2r8c2 2r9c6 3r2c6 3r4c9 3r5c3 3r8c5 3r8c8
+		3r9c9 4r1c6 4r2c2 6r1c2 6r3c4 6r5c1 6r8c6 6r9c3 8r2c5 8r3c2 8r8c9 8r9c4",
+		"",
+	);
+}
+
+#[test]
+fn unit_forcing_net() {
+	let mut grid = state(
+		"3 89 89 6 5 7 4 2 1
+		6 7 4 9 2 1 3 8 5
+		1 2 5 8 3 4 7 6 9
+		59 359 2 7 8 39 1 4 6
+		489 489 1 2 6 5 89 3 7
+		7 6 389 4 1 39 89 5 2
+		2 14 38 13 7 6 5 9 348
+		45 145 6 13 9 8 2 7 34
+		89 389 7 5 4 2 6 1 38",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&first(find(&analysis, "unit forcing net")),
+		"389657421674921385125834769592783146841265937763419852218376594456198273937542618",
+		"8r1c3 8r5c2 8r9c2 9r1c2 9r6c3",
+		"",
+	);
+}
+
+#[test]
+fn nishio() {
+	let mut grid = state(
+		"4 8 6 5 2 9 1 7 3
+		7 5 9 14 3 14 8 26 26
+		12 12 3 7 6 8 4 5 9
+		6 9 12 8 4 3 5 12 7
+		125 4 7 6 9 25 3 8 12
+		2358 23 58 12 7 125 6 9 4
+		19 67 12 49 5 46 27 3 8
+		2358 36 58 23 1 7 9 4 56
+		359 37 4 39 8 26 27 16 15",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "nishio"),
+		"486529173759431862213768459691843527547692381328175694162954738835217946974386215",
+		"1r3c1",
+		"",
+	);
+}
+
+#[test]
+fn templates() {
+	let mut grid = state(
+		"7 8 39 14 6 149 139 2 5
+		26 1 5 7 3 289 69 89 4
+		236 4 2369 5 18 1289 1369 189 7
+		5 27 1268 9 148 68 147 14 3
+		4 9 13 13 7 5 2 6 8
+		168 37 168 2 148 1368 147 5 9
+		9 5 7 146 2 14 8 3 16
+		128 23 4 168 9 13 5 7 126
+		1238 6 1238 138 5 7 49 49 12",
+	);
+	let analysis = Analysis::new(&mut grid, false);
+
+	assert_changes(
+		&analysis,
+		&find(&analysis, "templates"),
+		"783469125215738694649512387572986413491375268836241759957624831124893576368157942",
+		"1r4c3",
+		"",
+	);
+}
+
+fn techniques(names: &[&str]) -> Vec<&'static dyn Technique> {
+	names.iter().map(|name| builtin(name).unwrap()).collect()
+}
+
+// the techniques of the applied steps, each listed once in the order it first shows up
+fn applied(analysis: &Analysis) -> Vec<&'static str> {
+	let mut applied = vec![];
+	for step in analysis.steps().iter() {
+		if !applied.contains(&step.technique) {
+			applied.push(step.technique);
+		}
+	}
+
+	return applied;
+}
+
+#[test]
+fn rounds_follow_the_given_order() {
+	let line = ".7.5.3.4.3.68.17..1..4.62...8....43...........57.....252.........8..4...9....8..5";
+
+	for order in [
+		["single in columns", "single in squares"],
+		["single in squares", "single in columns"],
+	] {
+		let mut grid = line.parse::<Grid>().unwrap();
+		let mut analysis = Analysis::new(&mut grid, false);
+		analysis.techniques = techniques(&order);
+
+		assert_ne!(analysis.round(), 0);
+		assert_eq!(applied(&analysis), order);
+	}
+}
+
+#[test]
+fn rounds_skip_unique_techniques_unless_told_the_solution_is_unique() {
+	let mut grid = state(
+		"35 24 24 6 38 1 79 589 57
+		37 8 9 5 37 2 4 6 1
+		57 6 1 48 9 47 2 58 3
+		1 39 8 49 2 349 5 7 6
+		4 39 6 7 5 8 139 139 2
+		2 5 7 1 6 39 389 349 48
+		8 24 245 3 17 6 17 45 9
+		9 7 34 2 18 5 6 134 48
+		6 1 35 89 4 79 378 2 57",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.techniques = techniques(&["unique rectangles"]);
+
+	assert_eq!(analysis.round(), 0);
+	assert_eq!(analysis.grid.get_candidates(&Vec2::new(2, 6)).len(), 3);
+
+	analysis.unique = true;
+
+	assert_ne!(analysis.round(), 0);
+	assert_eq!(applied(&analysis), vec!["unique rectangle type 1"]);
+	assert!(!analysis.grid.get_candidates(&Vec2::new(2, 6)).contains(2));
+}
+
+#[test]
+fn rounds_skip_last_resorts_once_something_else_made_progress() {
+	let mut grid = state(
+		"4 8 6 5 2 9 1 7 3
+		7 5 9 14 3 14 8 26 26
+		12 12 3 7 6 8 4 5 9
+		6 9 12 8 4 3 5 12 7
+		125 4 7 6 9 25 3 8 12
+		2358 23 58 12 7 125 6 9 4
+		19 67 12 49 5 46 27 3 8
+		2358 36 58 23 1 7 9 4 56
+		359 37 4 39 8 26 27 16 15",
+	);
+	let mut analysis = Analysis::new(&mut grid, false);
+	analysis.techniques = techniques(&["unique rectangles", "nishio"]);
+	analysis.unique = true;
+
+	assert_ne!(analysis.round(), 0);
+	assert_eq!(applied(&analysis), vec!["unique rectangle type 4"]);
+	assert!(analysis.grid.get_candidates(&Vec2::new(0, 2)).contains(1));
+
+	// with nothing left for the unique rectangle, the next round falls back on nishio
+	assert_ne!(analysis.round(), 0);
+	assert_eq!(
+		applied(&analysis),
+		vec!["unique rectangle type 4", "nishio"]
+	);
+}